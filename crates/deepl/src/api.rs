@@ -1,14 +1,20 @@
-use crate::{Error, Lang, Result};
+use crate::{Error, Lang, Result, TargetLang};
 use reqwest::{Client, RequestBuilder};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt, str::FromStr};
+use std::{collections::HashMap, fmt, path::Path, str::FromStr, sync::Mutex, time::Duration};
+use tokio::time::Instant;
 use url::Url;
 
 const ENDPOINT_FREE: &str = "https://api-free.deepl.com";
 const ENDPOINT_PRO: &str = "https://api.deepl.com";
+const API_VERSION: &str = "v2";
+const USER_AGENT: &str = concat!("deepl-pro/", env!("CARGO_PKG_VERSION"));
+
+/// Maximum number of texts accepted in a single translate request.
+pub const MAX_TEXTS_PER_REQUEST: usize = 50;
 
 /// Enumeration of split sentence options.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SplitSentences {
     /// Do not split sentences.
     #[serde(rename = "0")]
@@ -26,7 +32,8 @@ pub enum SplitSentences {
 }
 
 /// Variants for formality.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Formality {
     /// Default formality.
     #[default]
@@ -47,13 +54,23 @@ pub enum Formality {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Language {
     /// Language code.
-    pub language: Lang,
+    ///
+    /// Codes are kept as strings as the API may return
+    /// languages that are not declared in [Lang].
+    pub language: String,
     /// Language name.
     pub name: String,
     /// Whether the language supports formality.
     pub supports_formality: Option<bool>,
 }
 
+impl Language {
+    /// Known language for the code.
+    pub fn lang(&self) -> Option<Lang> {
+        self.language.parse().ok()
+    }
+}
+
 /// Enumeration of language types.
 #[derive(Debug, Default, Serialize, Deserialize, Copy, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -92,6 +109,16 @@ impl FromStr for LanguageType {
     }
 }
 
+/// Variants for the account plan.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Plan {
+    /// Free plan with a fixed character limit.
+    Free,
+    /// Pro plan that may allow overage.
+    Pro,
+}
+
 /// Account usage information.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Usage {
@@ -99,10 +126,26 @@ pub struct Usage {
     pub character_count: u64,
     /// Character limit.
     pub character_limit: u64,
+    /// Account plan.
+    ///
+    /// Determined by the API key used for the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Plan>,
 }
 
-/// Variants for tag handling.
+/// Account usage and supported languages.
 #[derive(Debug, Serialize, Deserialize)]
+pub struct AccountStatus {
+    /// Account usage.
+    pub usage: Usage,
+    /// Supported source languages.
+    pub source_languages: Vec<Language>,
+    /// Supported target languages.
+    pub target_languages: Vec<Language>,
+}
+
+/// Variants for tag handling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TagHandling {
     /// XML tag handling.
@@ -121,12 +164,12 @@ pub struct TextTranslation {
 }
 
 /// Request to translate text.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslateTextRequest {
     /// Text to translate.
     pub text: Vec<String>,
     /// Target language.
-    pub target_lang: Lang,
+    pub target_lang: TargetLang,
     /// Tag handling.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag_handling: Option<TagHandling>,
@@ -164,10 +207,10 @@ pub struct TranslateTextRequest {
 
 impl TranslateTextRequest {
     /// Create new translate text request.
-    pub fn new(text: Vec<String>, target_lang: Lang) -> Self {
+    pub fn new(text: Vec<String>, target_lang: impl Into<TargetLang>) -> Self {
         Self {
             text,
-            target_lang,
+            target_lang: target_lang.into(),
             source_lang: None,
             context: None,
             preserve_formatting: None,
@@ -190,14 +233,184 @@ pub struct TranslateTextResponse {
     pub translations: Vec<TextTranslation>,
 }
 
+/// Format for glossary entries.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GlossaryEntriesFormat {
+    /// Tab-separated values.
+    #[default]
+    Tsv,
+    /// Comma-separated values.
+    Csv,
+}
+
+/// Request to create a glossary.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGlossaryRequest {
+    /// Name of the glossary.
+    pub name: String,
+    /// Source language.
+    pub source_lang: Lang,
+    /// Target language.
+    pub target_lang: Lang,
+    /// Glossary entries.
+    pub entries: String,
+    /// Format of the glossary entries.
+    pub entries_format: GlossaryEntriesFormat,
+}
+
+impl CreateGlossaryRequest {
+    /// Create a glossary request from source and target pairs.
+    ///
+    /// Pairs are encoded as tab-separated values; regional
+    /// languages are converted to the base language.
+    pub fn new(
+        name: impl AsRef<str>,
+        source_lang: Lang,
+        target_lang: Lang,
+        entries: &[(String, String)],
+    ) -> Self {
+        let entries = entries
+            .iter()
+            .map(|(source, target)| format!("{}\t{}", source, target))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self {
+            name: name.as_ref().to_owned(),
+            source_lang: source_lang.base(),
+            target_lang: target_lang.base(),
+            entries,
+            entries_format: GlossaryEntriesFormat::Tsv,
+        }
+    }
+}
+
+impl GlossaryEntriesFormat {
+    /// Determine the format from a file extension, files
+    /// with a `.csv` extension are comma-separated.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Tsv,
+        }
+    }
+
+    /// Parse and validate glossary entries.
+    ///
+    /// Every non-empty line must contain a non-empty source
+    /// and target term.
+    pub fn parse(&self, content: &str) -> Result<Vec<(String, String)>> {
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = match self {
+                Self::Tsv => line.split('\t').map(|s| s.to_owned()).collect(),
+                Self::Csv => split_csv_line(line).ok_or_else(|| {
+                    Error::InvalidGlossaryEntry(index + 1, "unterminated quote".to_owned())
+                })?,
+            };
+            let [source, target] = fields.as_slice() else {
+                return Err(Error::InvalidGlossaryEntry(
+                    index + 1,
+                    format!("expected 2 fields but got {}", fields.len()),
+                ));
+            };
+            let (source, target) = (source.trim(), target.trim());
+            if source.is_empty() || target.is_empty() {
+                return Err(Error::InvalidGlossaryEntry(
+                    index + 1,
+                    "empty term".to_owned(),
+                ));
+            }
+            entries.push((source.to_owned(), target.to_owned()));
+        }
+        Ok(entries)
+    }
+}
+
+/// Split a CSV line into fields allowing double quoted fields.
+fn split_csv_line(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+/// Glossary information.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Glossary {
+    /// Glossary identifier.
+    pub glossary_id: String,
+    /// Name of the glossary.
+    pub name: String,
+    /// Whether the glossary can be used.
+    pub ready: bool,
+    /// Source language.
+    pub source_lang: String,
+    /// Target language.
+    pub target_lang: String,
+    /// Creation time.
+    pub creation_time: String,
+    /// Number of entries.
+    pub entry_count: u64,
+}
+
+/// Error response body.
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
 /// Options when creating an API endpoint.
+///
+/// The connection settings are only used when a custom
+/// HTTP client has not been supplied.
 pub struct ApiOptions {
     /// API key.
     api_key: String,
     /// Endpoint URL.
     endpoint: Url,
+    /// Path prefix for API requests.
+    api_version: String,
+    /// User agent sent with requests.
+    user_agent: String,
     /// Custom HTTP client.
     client: Option<Client>,
+    /// Maximum idle connections per host.
+    pool_max_idle_per_host: Option<usize>,
+    /// Only use HTTP/2.
+    http2_prior_knowledge: bool,
+    /// TCP keepalive interval.
+    tcp_keepalive: Option<Duration>,
+    /// Maximum requests per second.
+    rate_limit: Option<f64>,
+    /// Request timeout.
+    timeout: Option<Duration>,
+    /// Maximum number of retries for failed requests.
+    max_retries: u32,
 }
 
 impl ApiOptions {
@@ -217,75 +430,394 @@ impl ApiOptions {
         options
     }
 
+    /// Account plan for the API key.
+    ///
+    /// Keys for the free plan use the `:fx` suffix so the plan
+    /// does not depend on a custom endpoint.
+    pub fn plan(&self) -> Plan {
+        if self.api_key.ends_with(":fx") {
+            Plan::Free
+        } else {
+            Plan::Pro
+        }
+    }
+
+    /// Endpoint URL.
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    /// Use a custom endpoint URL.
+    pub fn with_endpoint(mut self, endpoint: Url) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// Path prefix for API requests.
+    pub fn api_version(&self) -> &str {
+        &self.api_version
+    }
+
+    /// Use a custom path prefix for API requests, defaults to `v2`.
+    ///
+    /// An empty prefix sends requests relative to the endpoint.
+    pub fn with_api_version(mut self, api_version: impl AsRef<str>) -> Self {
+        self.api_version = api_version.as_ref().trim_matches('/').to_owned();
+        self
+    }
+
+    /// User agent sent with requests.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Use a custom user agent, defaults to `deepl-pro/<version>`.
+    pub fn with_user_agent(mut self, user_agent: impl AsRef<str>) -> Self {
+        self.user_agent = user_agent.as_ref().to_owned();
+        self
+    }
+
+    /// Set the request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of retries.
+    ///
+    /// Requests are retried with an exponential backoff when
+    /// rate limited, on server errors and on connection errors.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the maximum number of idle connections per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Only use HTTP/2 connections.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Set the TCP keepalive interval.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Limit the number of requests per second.
+    ///
+    /// Requests are paced client-side so they are spread
+    /// evenly; values that are not positive are ignored.
+    pub fn rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self
+    }
+
     /// API for the free endpoint.
     fn new_free(api_key: impl AsRef<str>) -> Self {
-        Self {
-            api_key: api_key.as_ref().to_owned(),
-            endpoint: Url::parse(ENDPOINT_FREE).unwrap(),
-            client: None,
-        }
+        Self::new_endpoint(api_key, ENDPOINT_FREE)
     }
 
     /// API for the pro endpoint.
     fn new_pro(api_key: impl AsRef<str>) -> Self {
+        Self::new_endpoint(api_key, ENDPOINT_PRO)
+    }
+
+    fn new_endpoint(api_key: impl AsRef<str>, endpoint: &str) -> Self {
         Self {
             api_key: api_key.as_ref().to_owned(),
-            endpoint: Url::parse(ENDPOINT_PRO).unwrap(),
+            endpoint: Url::parse(endpoint).unwrap(),
+            api_version: API_VERSION.to_owned(),
+            user_agent: USER_AGENT.to_owned(),
             client: None,
+            pool_max_idle_per_host: None,
+            http2_prior_knowledge: false,
+            tcp_keepalive: None,
+            rate_limit: None,
+            timeout: None,
+            max_retries: 0,
+        }
+    }
+
+    /// Build an HTTP client from the connection settings.
+    fn build_client(&self) -> Client {
+        let mut builder = Client::builder();
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
         }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder.build().expect("failed to build HTTP client")
+    }
+
+    /// Minimum interval between requests.
+    fn request_interval(&self) -> Option<Duration> {
+        self.rate_limit
+            .filter(|rps| rps.is_finite() && *rps > 0.0)
+            .map(|rps| Duration::from_secs_f64(1.0 / rps))
     }
 }
 
 /// Interface to the DeepL API.
+///
+/// The HTTP client is created once and shared by all requests
+/// so connections are reused between calls.
 pub struct DeeplApi {
     client: Client,
     options: ApiOptions,
+    interval: Option<Duration>,
+    next_request: Mutex<Option<Instant>>,
 }
 
 impl DeeplApi {
     /// Create a new DeepL API client.
+    ///
+    /// # Panics
+    ///
+    /// If no client was supplied and the TLS backend
+    /// cannot be initialized.
     pub fn new(mut options: ApiOptions) -> Self {
         Self {
-            client: options.client.take().unwrap_or_else(|| Client::new()),
+            client: options
+                .client
+                .take()
+                .unwrap_or_else(|| options.build_client()),
+            interval: options.request_interval(),
+            next_request: Mutex::new(None),
             options,
         }
     }
 
+    /// Account plan for the API key.
+    pub fn plan(&self) -> Plan {
+        self.options.plan()
+    }
+
+    /// Endpoint URL used for requests.
+    pub fn endpoint(&self) -> &Url {
+        self.options.endpoint()
+    }
+
     /// Get account usage.
     pub async fn usage(&self) -> Result<Usage> {
-        let url = self.options.endpoint.join("v2/usage")?;
+        let url = self.url("usage")?;
         let req = self.client.get(url);
-        self.make_typed_request::<Usage>(req).await
+        let mut usage = self.make_typed_request::<Usage>(req).await?;
+        usage.plan = Some(self.plan());
+        Ok(usage)
+    }
+
+    /// Fetch usage and the supported source and target
+    /// languages concurrently.
+    pub async fn account_status(&self) -> Result<AccountStatus> {
+        let (usage, source_languages, target_languages) = tokio::try_join!(
+            self.usage(),
+            self.languages(LanguageType::Source),
+            self.languages(LanguageType::Target),
+        )?;
+        Ok(AccountStatus {
+            usage,
+            source_languages,
+            target_languages,
+        })
     }
 
     /// Fetch supported languages.
     pub async fn languages(&self, lang_type: LanguageType) -> Result<Vec<Language>> {
-        let mut url = self.options.endpoint.join("v2/languages")?;
+        let mut url = self.url("languages")?;
         url.query_pairs_mut()
             .append_pair("type", lang_type.as_ref());
         let req = self.client.get(url);
         self.make_typed_request::<Vec<Language>>(req).await
     }
 
+    /// Fetch supported languages keyed by language.
+    ///
+    /// Languages that are not declared in [Lang] are ignored.
+    pub async fn languages_map(&self, lang_type: LanguageType) -> Result<HashMap<Lang, Language>> {
+        Ok(self
+            .languages(lang_type)
+            .await?
+            .into_iter()
+            .filter_map(|language| language.lang().map(|lang| (lang, language)))
+            .collect())
+    }
+
     /// Translate text.
     pub async fn translate_text(
         &self,
         request: &TranslateTextRequest,
     ) -> Result<TranslateTextResponse> {
-        let url = self.options.endpoint.join("v2/translate")?;
+        let url = self.url("translate")?;
+        let req = self.client.post(url).json(request);
+        match self.make_typed_request::<TranslateTextResponse>(req).await {
+            Err(Error::Api(400, message))
+                if request.formality.is_some() && is_formality_unsupported(&message) =>
+            {
+                Err(Error::FormalityUnsupported(request.target_lang.clone()))
+            }
+            result => result,
+        }
+    }
+
+    /// Translate keyed texts returning the translations paired with
+    /// their keys in the order they were given.
+    ///
+    /// The texts are split into requests of at most
+    /// [MAX_TEXTS_PER_REQUEST] items; all other request fields are
+    /// copied from `options` and any text it contains is ignored.
+    pub async fn translate_keyed<K>(
+        &self,
+        entries: Vec<(K, String)>,
+        options: &TranslateTextRequest,
+    ) -> Result<Vec<(K, String)>> {
+        let mut results = Vec::with_capacity(entries.len());
+        let mut entries = entries.into_iter().peekable();
+        while entries.peek().is_some() {
+            let (keys, text): (Vec<K>, Vec<String>) =
+                entries.by_ref().take(MAX_TEXTS_PER_REQUEST).unzip();
+            let mut request = options.clone();
+            request.text = text;
+            let response = self.translate_text(&request).await?;
+            if response.translations.len() != keys.len() {
+                return Err(Error::TranslationCount(
+                    keys.len(),
+                    response.translations.len(),
+                ));
+            }
+            results.extend(
+                keys.into_iter()
+                    .zip(response.translations.into_iter().map(|t| t.text)),
+            );
+        }
+        Ok(results)
+    }
+
+    /// Create a glossary.
+    pub async fn create_glossary(&self, request: &CreateGlossaryRequest) -> Result<Glossary> {
+        let url = self.url("glossaries")?;
         let req = self.client.post(url).json(request);
-        self.make_typed_request::<TranslateTextResponse>(req).await
+        self.make_typed_request::<Glossary>(req).await
     }
 
-    async fn make_typed_request<T: DeserializeOwned>(&self, req: RequestBuilder) -> Result<T> {
+    /// Create a glossary from a local file of term pairs.
+    ///
+    /// Files with a `.csv` extension are read as comma-separated
+    /// values otherwise tab-separated values are expected; the
+    /// entries are validated before the request is sent.
+    pub async fn create_glossary_from_tsv(
+        &self,
+        name: impl AsRef<str>,
+        source_lang: Lang,
+        target_lang: Lang,
+        path: impl AsRef<Path>,
+    ) -> Result<Glossary> {
+        let format = GlossaryEntriesFormat::from_path(path.as_ref());
+        let content = std::fs::read_to_string(path.as_ref())?;
+        let entries = format.parse(&content)?;
+        if entries.is_empty() {
+            return Err(Error::EmptyGlossary);
+        }
+        let request = CreateGlossaryRequest::new(name, source_lang, target_lang, &entries);
+        self.create_glossary(&request).await
+    }
+
+    /// URL for an API path using the configured prefix.
+    fn url(&self, path: &str) -> Result<Url> {
+        if self.options.api_version.is_empty() {
+            Ok(self.options.endpoint.join(path)?)
+        } else {
+            Ok(self
+                .options
+                .endpoint
+                .join(&format!("{}/{}", self.options.api_version, path))?)
+        }
+    }
+
+    /// Wait until the rate limit allows another request.
+    async fn throttle(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let deadline = {
+            let mut next_request = self.next_request.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let deadline = next_request.map(|next| next.max(now)).unwrap_or(now);
+            *next_request = Some(deadline + interval);
+            deadline
+        };
+        tokio::time::sleep_until(deadline).await;
+    }
+
+    async fn make_typed_request<T: DeserializeOwned>(&self, mut req: RequestBuilder) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            let retry = if attempt < self.options.max_retries {
+                req.try_clone()
+            } else {
+                None
+            };
+            match (self.send_typed_request(req).await, retry) {
+                (Err(e), Some(next)) if is_retryable(&e) => {
+                    let delay = Duration::from_millis(500 * 2u64.pow(attempt));
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                    req = next;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+
+    async fn send_typed_request<T: DeserializeOwned>(&self, req: RequestBuilder) -> Result<T> {
+        self.throttle().await;
         let res = req
             .header(
                 "Authorization",
                 format!("DeepL-Auth-Key {}", self.options.api_key),
             )
+            .header("User-Agent", &self.options.user_agent)
             .send()
             .await?;
-        res.error_for_status_ref()?;
+        let status = res.status();
+        if status.is_client_error() || status.is_server_error() {
+            let message = res
+                .json::<ErrorBody>()
+                .await
+                .map(|body| body.message)
+                .unwrap_or_else(|_| status.to_string());
+            return Err(Error::Api(status.as_u16(), message));
+        }
         Ok(res.json::<T>().await?)
     }
 }
+
+/// Whether an error message reports that the target language
+/// does not support formality, for example "'formality' is not
+/// supported for given 'target_lang'".
+fn is_formality_unsupported(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("formality") && message.contains("not supported")
+}
+
+/// Whether a request should be retried after an error.
+fn is_retryable(error: &Error) -> bool {
+    match error {
+        Error::Api(status, _) => *status == 429 || *status >= 500,
+        Error::Http(e) => e.is_timeout() || e.is_connect(),
+        _ => false,
+    }
+}
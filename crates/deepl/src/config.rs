@@ -0,0 +1,31 @@
+/// Parse the API key from the content of a DeepL config file.
+///
+/// The file may contain the bare key on a line or an
+/// `auth_key = <key>` line; comments and INI section headers
+/// such as `[auth]` are ignored.
+pub fn parse_config_api_key(content: &str) -> Option<String> {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            continue;
+        }
+        let value = match line.split_once('=') {
+            Some((name, value)) => {
+                let name = name.trim().to_lowercase().replace('-', "_");
+                if !matches!(name.as_str(), "auth_key" | "api_key") {
+                    continue;
+                }
+                value
+            }
+            None => line,
+        };
+        let value = value.trim().trim_matches(['"', '\'']);
+        if !value.is_empty() {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
@@ -1,3 +1,4 @@
+use crate::TargetLang;
 use thiserror::Error;
 
 /// Errors generated by the library.
@@ -9,6 +10,27 @@ pub enum Error {
     /// Error generated when a language type is invalid.
     #[error("invalid language type '{0}'")]
     InvalidLanguageType(String),
+    /// Error generated when the API returns an error status.
+    #[error("API error {0}: {1}")]
+    Api(u16, String),
+    /// Error generated when formality is requested for a
+    /// language that does not support it.
+    #[error("formality is not supported for '{0}'")]
+    FormalityUnsupported(TargetLang),
+    /// Error generated when the number of translations in a
+    /// response does not match the number of texts sent.
+    #[error("expected {0} translations but got {1}")]
+    TranslationCount(usize, usize),
+    /// Error generated when a glossary file contains
+    /// an invalid line.
+    #[error("invalid glossary entry on line {0}: {1}")]
+    InvalidGlossaryEntry(usize, String),
+    /// Error generated when a glossary has no entries.
+    #[error("glossary has no entries")]
+    EmptyGlossary,
+    /// Error generated by the io module.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     /// Error generated by the URL library.
     #[error(transparent)]
     Url(#[from] url::ParseError),
@@ -2,10 +2,22 @@ use crate::Error;
 use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 
-/// Languages supported by the DeepL API.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Hash)]
-#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
-pub enum Lang {
+/// Declare the languages and the list of all languages from
+/// the same variants so the list cannot drift from the enum.
+macro_rules! languages {
+    ($($(#[$meta:meta])* $variant:ident,)+) => {
+        /// Languages supported by the DeepL API.
+        #[derive(Debug, Copy, Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Hash)]
+        #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+        pub enum Lang {
+            $($(#[$meta])* $variant,)+
+        }
+
+        const ALL_LANGUAGES: &[Lang] = &[$(Lang::$variant,)+];
+    };
+}
+
+languages! {
     /// Arabic.
     Ar,
     /// Bulgarian.
@@ -76,6 +88,25 @@ pub enum Lang {
     Zh,
 }
 
+impl Lang {
+    /// All supported languages.
+    pub fn all() -> &'static [Lang] {
+        ALL_LANGUAGES
+    }
+
+    /// Language without a regional variant.
+    ///
+    /// Source languages and glossary language pairs only
+    /// accept base languages, for example `EN` for `EN-US`.
+    pub fn base(&self) -> Lang {
+        match self {
+            Self::EnGb | Self::EnUs => Self::En,
+            Self::PtBr | Self::PtPt => Self::Pt,
+            lang => *lang,
+        }
+    }
+}
+
 impl fmt::Display for Lang {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -166,3 +197,45 @@ impl FromStr for Lang {
         })
     }
 }
+
+/// Target language for a translation.
+///
+/// Raw language codes are passed through to the API so that
+/// languages supported by DeepL but not yet declared in [Lang]
+/// may be used.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TargetLang {
+    /// Known language.
+    Lang(Lang),
+    /// Raw language code.
+    Code(String),
+}
+
+impl TargetLang {
+    /// Determine if this target is the given language.
+    ///
+    /// Raw codes are parsed so that a code naming a known
+    /// language (for example `en`) matches that language.
+    pub fn is_lang(&self, lang: Lang) -> bool {
+        match self {
+            Self::Lang(value) => *value == lang,
+            Self::Code(code) => code.parse::<Lang>().ok() == Some(lang),
+        }
+    }
+}
+
+impl From<Lang> for TargetLang {
+    fn from(value: Lang) -> Self {
+        Self::Lang(value)
+    }
+}
+
+impl fmt::Display for TargetLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lang(lang) => write!(f, "{}", lang),
+            Self::Code(code) => write!(f, "{}", code.to_uppercase()),
+        }
+    }
+}
@@ -3,15 +3,18 @@
 #![forbid(unsafe_code)]
 
 mod api;
+mod config;
 mod error;
 mod lang;
 
 pub use api::{
-    ApiOptions, DeeplApi, Formality, Language, LanguageType, SplitSentences, TagHandling,
-    TranslateTextRequest, TranslateTextResponse, Usage,
+    AccountStatus, ApiOptions, CreateGlossaryRequest, DeeplApi, Formality, Glossary,
+    GlossaryEntriesFormat, Language, LanguageType, Plan, SplitSentences, TagHandling,
+    TextTranslation, TranslateTextRequest, TranslateTextResponse, Usage, MAX_TEXTS_PER_REQUEST,
 };
+pub use config::parse_config_api_key;
 pub use error::Error;
-pub use lang::Lang;
+pub use lang::{Lang, TargetLang};
 
 /// Result type for the library.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,25 +1,163 @@
 use super::{Error, Result};
+use crate::{icu::placeholder_names, IcuMessage, IcuNode};
+use deepl::Formality;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashSet, fmt};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
 
 const PLACEHOLDERS: &str = "placeholders";
+const CONTEXT: &str = "context";
+const DESCRIPTION: &str = "description";
+const LOCKED: &str = "x-locked";
+const TRANSLATE: &str = "x-translate";
+const TRACK_DESCRIPTION: &str = "x-track-description";
+const LOCALE: &str = "@@locale";
+const DIRECTIVE_PREFIX: &str = "@@x-arb-";
+const FORMALITY_DIRECTIVE: &str = "@@x-arb-formality";
+const CONTEXT_DIRECTIVE: &str = "@@x-arb-context";
+const EXCLUDE_DIRECTIVE: &str = "@@x-arb-exclude";
+const SOURCE_HASH: &str = "x-source-hash";
+const TRANSLATOR_NOTE: &str = "x-translator-note";
+
+fn translator_note_field(lang: &str) -> String {
+    format!("{}-{}", TRANSLATOR_NOTE, lang.to_lowercase())
+}
+
+/// Hash of a source value.
+///
+/// Uses the 64-bit FNV-1a algorithm so the hash is stable
+/// across platforms and compiler versions.
+pub fn hash_source(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Tool configuration declared in a template using
+/// `@@x-arb-` global meta data.
+#[derive(Debug, Default, Clone)]
+pub struct ToolConfig {
+    /// Default formality from `@@x-arb-formality`.
+    pub formality: Option<Formality>,
+    /// Global context from `@@x-arb-context`.
+    pub context: Option<String>,
+    /// Keys copied without translation from `@@x-arb-exclude`.
+    pub exclude: Vec<String>,
+}
 
 /// Diff of the keys in two language files.
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Keys are serialized in sorted order so the output is stable.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct FileDiff {
     /// Set of keys that exist in the template but
     /// not in the target language.
+    #[serde(serialize_with = "serialize_sorted")]
     pub create: HashSet<String>,
     /// Set of keys that exist in the target language
     /// but not in the template.
+    #[serde(serialize_with = "serialize_sorted")]
     pub delete: HashSet<String>,
     /// Set of keys that have changed in the template
     /// since the last translation.
+    #[serde(serialize_with = "serialize_sorted")]
     pub update: HashSet<String>,
 }
 
+/// Serialize a set of keys in sorted order.
+fn serialize_sorted<S>(
+    keys: &HashSet<String>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut keys = keys.iter().collect::<Vec<_>>();
+    keys.sort();
+    keys.serialize(serializer)
+}
+
+impl FileDiff {
+    /// Created and updated keys with their source text from
+    /// the template, in template order.
+    pub fn entries(&self, template: &ArbFile) -> Vec<DiffEntry> {
+        let mut entries = Vec::new();
+        for (key, value) in &template.contents {
+            let status = if self.create.contains(key) {
+                DiffStatus::Create
+            } else if self.update.contains(key) {
+                DiffStatus::Update
+            } else {
+                continue;
+            };
+            if ArbKey::new(key).is_prefixed() {
+                continue;
+            }
+            entries.push(DiffEntry {
+                key: key.to_owned(),
+                source: value.as_str().map(|s| s.to_owned()),
+                status,
+            });
+        }
+        entries
+    }
+}
+
+/// Status of a key in a diff.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    /// Key does not exist in the target language.
+    Create,
+    /// Key has changed in the template.
+    Update,
+}
+
+/// Key in a diff with the source text from the template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffEntry {
+    /// Key name.
+    pub key: String,
+    /// Source text from the template.
+    pub source: Option<String>,
+    /// Whether the key is created or updated.
+    pub status: DiffStatus,
+}
+
+/// Entry in a review export.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewEntry {
+    /// Source text from the template.
+    pub source: String,
+    /// Translated text.
+    pub target: Option<String>,
+    /// Description from the template meta data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Placeholder usage statistics for a template.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PlaceholderStats {
+    /// Placeholder names for each key that uses placeholders.
+    pub keys: IndexMap<String, Vec<String>>,
+    /// Number of keys using each placeholder name.
+    pub names: BTreeMap<String, usize>,
+    /// Number of translatable keys.
+    pub total_keys: usize,
+    /// Largest number of placeholders in a single key.
+    pub max: usize,
+    /// Average number of placeholders for each translatable key.
+    pub mean: f64,
+}
+
 /// Content of an application resource bundle file.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ArbFile {
@@ -38,6 +176,16 @@ impl ArbFile {
         self.contents.is_empty()
     }
 
+    /// Whether the application resource bundle contains a key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.contents.contains_key(key)
+    }
+
+    /// Position of a key in the application resource bundle.
+    pub fn index_of(&self, key: &str) -> Option<usize> {
+        self.contents.get_index_of(key)
+    }
+
     /// All of the application resource bundle entries.
     pub fn entries(&self) -> Vec<ArbEntry<'_>> {
         self.contents
@@ -46,6 +194,14 @@ impl ArbFile {
             .collect()
     }
 
+    /// Entries with a translatable key and string value.
+    pub fn translatable_entries(&self) -> Vec<ArbEntry<'_>> {
+        self.entries()
+            .into_iter()
+            .filter(|entry| entry.is_translatable())
+            .collect()
+    }
+
     /// Lookup an entry by key.
     pub fn lookup<'a>(&'a self, key: &'a str) -> Option<ArbEntry<'a>> {
         self.contents
@@ -70,26 +226,424 @@ impl ArbFile {
             .insert(entry.key().to_string(), entry.value().into());
     }
 
+    /// Determine if two files have the same keys and values
+    /// regardless of the order of the keys.
+    pub fn content_eq(&self, other: &ArbFile) -> bool {
+        self.contents.len() == other.contents.len()
+            && self
+                .contents
+                .iter()
+                .all(|(key, value)| other.contents.get(key) == Some(value))
+    }
+
+    /// Determine if a key declares meta data for another key.
+    pub fn is_metadata_key(key: &str) -> bool {
+        ArbKey::new(key).is_metadata()
+    }
+
+    /// Append the entries from another file.
+    ///
+    /// Global `@@` entries in the other file are ignored and
+    /// existing keys are not replaced.
+    pub fn extend(&mut self, other: ArbFile) {
+        for (key, value) in other.contents {
+            if ArbKey::new(&key).is_global_metadata() {
+                continue;
+            }
+            if self.contents.contains_key(&key) {
+                tracing::warn!(key = %key, "duplicate key");
+                continue;
+            }
+            self.contents.insert(key, value);
+        }
+    }
+
+    /// Create a template from a translated file.
+    ///
+    /// Keys and meta data are kept and translatable values
+    /// are replaced with empty strings; the `@@locale` of the
+    /// translation is removed.
+    pub fn to_template(&self) -> ArbFile {
+        let mut template = ArbFile::default();
+        for entry in self.entries() {
+            let key = entry.key();
+            if key.as_ref() == LOCALE {
+                continue;
+            }
+            let value = if entry.is_translatable() {
+                Value::String(String::new())
+            } else {
+                entry.value().into()
+            };
+            template.contents.insert(key.to_string(), value);
+        }
+        template
+    }
+
+    /// Placeholder usage statistics for the translatable keys.
+    pub fn placeholder_stats(&self) -> PlaceholderStats {
+        let mut stats = PlaceholderStats::default();
+        let mut total = 0;
+        for entry in self.translatable_entries() {
+            stats.total_keys += 1;
+            let names = entry.value().placeholder_names();
+            if names.is_empty() {
+                continue;
+            }
+            total += names.len();
+            stats.max = stats.max.max(names.len());
+            for name in &names {
+                *stats.names.entry(name.to_owned()).or_default() += 1;
+            }
+            stats.keys.insert(entry.key().to_string(), names);
+        }
+        if stats.total_keys > 0 {
+            stats.mean = total as f64 / stats.total_keys as f64;
+        }
+        stats
+    }
+
+    /// Split entries belonging to keys in another file
+    /// into a new file.
+    ///
+    /// Meta data entries follow their data key.
+    pub fn split_off(&mut self, keys: &ArbFile) -> ArbFile {
+        let mut output = ArbFile::default();
+        self.contents.retain(|key, value| {
+            let name = ArbKey::new(key);
+            let data_key = name.data_key().unwrap_or(key);
+            if !name.is_global_metadata()
+                && (keys.contents.contains_key(key) || keys.contents.contains_key(data_key))
+            {
+                output.contents.insert(key.to_owned(), value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        output
+    }
+
+    /// Sort the keys alphabetically keeping each meta data
+    /// entry immediately after the key it describes.
+    ///
+    /// Global `@@` entries are moved to the start and keep
+    /// their relative position.
+    pub fn sort_keys(&mut self) {
+        fn sort_key(key: &str) -> (bool, &str, bool) {
+            let arb_key = ArbKey::new(key);
+            if arb_key.is_global_metadata() {
+                (false, "", false)
+            } else {
+                match arb_key.data_key() {
+                    Some(data_key) => (true, data_key, true),
+                    None => (true, key, false),
+                }
+            }
+        }
+        self.contents
+            .sort_by(|a, _, b, _| sort_key(a).cmp(&sort_key(b)));
+    }
+
+    /// Move each meta data entry so it immediately follows
+    /// the key it describes.
+    ///
+    /// Meta data without a corresponding key and global `@@`
+    /// entries keep their relative position.
+    pub fn group_metadata(&mut self) {
+        let mut contents = std::mem::take(&mut self.contents);
+        let meta_keys: Vec<String> = contents
+            .keys()
+            .filter(|key| {
+                ArbKey::new(key)
+                    .data_key()
+                    .map(|data_key| contents.contains_key(data_key))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+        let mut metadata = IndexMap::new();
+        for key in meta_keys {
+            if let Some(value) = contents.shift_remove(&key) {
+                metadata.insert(key, value);
+            }
+        }
+        for (key, value) in contents {
+            let meta_key = format!("@{}", key);
+            self.contents.insert(key, value);
+            if let Some(meta) = metadata.shift_remove(&meta_key) {
+                self.contents.insert(meta_key, meta);
+            }
+        }
+    }
+
     /// Remove an entry.
     pub fn remove(&mut self, key: &str) -> Option<Value> {
         self.contents.shift_remove(key)
     }
 
+    /// Rename a key and the meta data for the key keeping
+    /// the position of both entries.
+    ///
+    /// An existing entry for the new key is replaced.
+    ///
+    /// Returns whether the key existed.
+    pub fn rename_key(&mut self, old: &str, new: &str) -> bool {
+        if !self.contents.contains_key(old) {
+            return false;
+        }
+        if old == new {
+            return true;
+        }
+        let old_meta = format!("@{}", old);
+        let new_meta = format!("@{}", new);
+        self.contents.shift_remove(new);
+        self.contents.shift_remove(&new_meta);
+        for (from, to) in [(old, new), (old_meta.as_str(), new_meta.as_str())] {
+            if let Some((index, _, value)) = self.contents.shift_remove_full(from) {
+                self.contents.shift_insert(index, to.to_owned(), value);
+            }
+        }
+        true
+    }
+
+    /// Retain only the entries specified by the predicate.
+    ///
+    /// Meta data keys (prefixed with an @ symbol) are kept or
+    /// removed along with the entry they describe; the predicate
+    /// is only called for meta data keys without a corresponding
+    /// entry. Global keys (prefixed with @@) are always retained.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&ArbKey<'_>, &ArbValue<'_>) -> bool,
+    {
+        let mut keep = HashMap::new();
+        for (key, value) in &self.contents {
+            if !ArbKey::new(key).is_prefixed() {
+                keep.insert(
+                    key.to_owned(),
+                    predicate(&ArbKey::new(key), &ArbValue::new(value)),
+                );
+            }
+        }
+
+        self.contents.retain(|key, value| {
+            let arb_key = ArbKey::new(key);
+            if arb_key.is_global_metadata() {
+                return true;
+            }
+            let name = arb_key.data_key().unwrap_or(key);
+            match keep.get(name) {
+                Some(retained) => *retained,
+                None => predicate(&ArbKey::new(key), &ArbValue::new(value)),
+            }
+        });
+    }
+
+    /// Context for a key from the meta data.
+    pub fn context(&self, key: &str) -> Option<&str> {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(CONTEXT).and_then(|value| value.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Review entries pairing each translatable key in this
+    /// template with the translated text.
+    pub fn review(&self, translated: &ArbFile) -> IndexMap<String, ReviewEntry> {
+        let mut output = IndexMap::new();
+        for entry in self.translatable_entries() {
+            if let Some(source) = entry.value().as_str() {
+                let key = entry.key().as_ref();
+                let target = translated
+                    .lookup(key)
+                    .and_then(|entry| entry.value().as_str().map(|s| s.to_owned()));
+                let description = self.description(key).map(|s| s.to_owned());
+                output.insert(
+                    key.to_owned(),
+                    ReviewEntry {
+                        source: source.to_owned(),
+                        target,
+                        description,
+                    },
+                );
+            }
+        }
+        output
+    }
+
+    /// Description declared in the meta data for a key.
+    pub fn description(&self, key: &str) -> Option<&str> {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(DESCRIPTION).and_then(|value| value.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Whether a key is locked by the `x-locked` flag in
+    /// the meta data for the key.
+    pub fn is_locked(&self, key: &str) -> bool {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(LOCKED)
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Whether a key is excluded from machine translation by
+    /// setting the `x-translate` flag to false in the meta data
+    /// for the key or listing the key in `@@x-arb-exclude`.
+    pub fn is_excluded(&self, key: &str) -> bool {
+        if let Some(Value::Array(keys)) = self.contents.get(EXCLUDE_DIRECTIVE) {
+            if keys.iter().any(|value| value.as_str() == Some(key)) {
+                return true;
+            }
+        }
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(TRANSLATE)
+                .and_then(|value| value.as_bool())
+                .map(|translate| !translate)
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Parse the tool configuration from the `@@x-arb-` global
+    /// meta data.
+    ///
+    /// Unrecognized directives are ignored with a warning.
+    pub fn tool_config(&self) -> Result<ToolConfig> {
+        let mut config = ToolConfig::default();
+        for (key, value) in &self.contents {
+            if !key.starts_with(DIRECTIVE_PREFIX) {
+                continue;
+            }
+            let invalid = || Error::InvalidDirective(key.to_owned(), value.to_string());
+            match key.as_str() {
+                FORMALITY_DIRECTIVE => {
+                    let formality = value.as_str().ok_or_else(invalid)?;
+                    config.formality =
+                        Some(match formality.to_lowercase().replace('_', "-").as_str() {
+                            "default" => Formality::Default,
+                            "more" => Formality::More,
+                            "less" => Formality::Less,
+                            "prefer-more" => Formality::PreferMore,
+                            "prefer-less" => Formality::PreferLess,
+                            _ => return Err(invalid()),
+                        });
+                }
+                CONTEXT_DIRECTIVE => {
+                    config.context = Some(value.as_str().ok_or_else(invalid)?.to_owned());
+                }
+                EXCLUDE_DIRECTIVE => {
+                    let keys = value.as_array().ok_or_else(invalid)?;
+                    for key in keys {
+                        config
+                            .exclude
+                            .push(key.as_str().ok_or_else(invalid)?.to_owned());
+                    }
+                }
+                _ => tracing::warn!(key = %key, "unknown directive"),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Whether changes to the description of a key should
+    /// cause the key to be translated again, enabled by the
+    /// `x-track-description` flag in the meta data for the key.
+    pub fn tracks_description(&self, key: &str) -> bool {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(TRACK_DESCRIPTION)
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Set the description in the meta data for a key.
+    pub fn set_description(&mut self, key: &str, description: impl Into<String>) {
+        self.set_meta(key, DESCRIPTION, Value::String(description.into()));
+    }
+
+    /// Hash of the source value stored in the meta data for
+    /// a key when it was translated.
+    pub fn source_hash(&self, key: &str) -> Option<&str> {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(SOURCE_HASH).and_then(|value| value.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Set the source hash in the meta data for a key.
+    pub fn set_source_hash(&mut self, key: &str, hash: impl Into<String>) {
+        self.set_meta(key, SOURCE_HASH, Value::String(hash.into()));
+    }
+
+    /// Translator note for a language in the meta data for a key.
+    pub fn translator_note(&self, key: &str, lang: &str) -> Option<&str> {
+        if let Some(Value::Object(map)) = self.contents.get(&format!("@{}", key)) {
+            map.get(&translator_note_field(lang))
+                .and_then(|value| value.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Set the translator note for a language in the meta
+    /// data for a key.
+    ///
+    /// Notes are stored in an `x-translator-note-<lang>` field
+    /// using the lowercase language code.
+    pub fn set_translator_note(&mut self, key: &str, lang: &str, note: impl Into<String>) {
+        self.set_meta(
+            key,
+            &translator_note_field(lang),
+            Value::String(note.into()),
+        );
+    }
+
+    /// Set a field in the meta data for a key, the meta data
+    /// is created after the key when it does not exist.
+    fn set_meta(&mut self, key: &str, field: &str, value: Value) {
+        let meta_key = format!("@{}", key);
+        match self.contents.get_mut(&meta_key) {
+            Some(Value::Object(map)) => {
+                map.insert(field.to_owned(), value);
+            }
+            _ => {
+                let mut map = serde_json::Map::new();
+                map.insert(field.to_owned(), value);
+                let value = Value::Object(map);
+                if let Some(index) = self.contents.get_index_of(&meta_key) {
+                    self.contents[index] = value;
+                } else if let Some(index) = self.index_of(key) {
+                    self.contents.shift_insert(index + 1, meta_key, value);
+                } else {
+                    self.contents.insert(meta_key, value);
+                }
+            }
+        }
+    }
+
     /// Attempt to locate the placeholder names for a key.
     pub fn placeholders<'a>(&self, key: &ArbKey<'a>) -> Result<Option<Placeholders<'_>>> {
-        if key.as_ref().starts_with('@') {
+        if key.is_prefixed() {
             return Err(Error::AlreadyPrefixed(key.to_string()));
         }
 
         let meta_key = format!("@{}", key.as_ref());
-        if let Some(value) = self.contents.get(&meta_key) {
-            if let Value::Object(map) = value {
-                if let Some(Value::Object(placeholders)) = map.get(PLACEHOLDERS) {
-                    let keys = placeholders.keys().map(|k| &k[..]).collect::<Vec<_>>();
-                    Ok(Some(Placeholders::new(keys)))
-                } else {
-                    Ok(None)
-                }
+        if let Some(Value::Object(map)) = self.contents.get(&meta_key) {
+            if let Some(Value::Object(placeholders)) = map.get(PLACEHOLDERS) {
+                let keys = placeholders.keys().map(|k| &k[..]).collect::<Vec<_>>();
+                Ok(Some(Placeholders::new(keys)))
             } else {
                 Ok(None)
             }
@@ -98,8 +652,75 @@ impl ArbFile {
         }
     }
 
+    /// Placeholder names for every translatable key.
+    ///
+    /// Names are read from the meta data for a key and when no
+    /// placeholders are declared the value is scanned for names
+    /// enclosed in curly braces.
+    pub fn all_placeholders(&self) -> HashMap<String, Vec<String>> {
+        let mut output = HashMap::new();
+        for entry in self.translatable_entries() {
+            let declared = self.placeholders(entry.key()).ok().flatten();
+            let names = if let Some(placeholders) = declared {
+                placeholders.names().iter().map(|s| s.to_string()).collect()
+            } else {
+                entry.value().placeholder_names()
+            };
+            output.insert(entry.key().to_string(), names);
+        }
+        output
+    }
+
+    /// Source and target pairs for a glossary derived from
+    /// human-provided overrides.
+    ///
+    /// Pairs are only created for translatable keys that exist in
+    /// both files; values containing tabs or newlines are skipped
+    /// and when the same source text appears more than once the
+    /// first pair is used.
+    pub fn glossary_entries(&self, overrides: &ArbFile) -> Vec<(String, String)> {
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::new();
+        let invalid = |s: &str| s.trim().is_empty() || s.contains(['\t', '\n', '\r']);
+        for (key, value) in &self.contents {
+            if let (false, Value::String(source), Some(Value::String(target))) = (
+                ArbKey::new(key).is_prefixed(),
+                value,
+                overrides.contents.get(key),
+            ) {
+                if invalid(source) || invalid(target) || !seen.insert(source) {
+                    continue;
+                }
+                pairs.push((source.to_owned(), target.to_owned()));
+            }
+        }
+        pairs
+    }
+
     /// Get a diff of keys between files.
     pub fn diff<'a>(&'a self, other: &'a ArbFile, cache: Option<&'a ArbFile>) -> FileDiff {
+        self.diff_keys(other, cache, false)
+    }
+
+    /// Get a diff of keys between files where values that only
+    /// differ by whitespace from the cached value are not updates.
+    ///
+    /// Runs of whitespace are collapsed and leading and trailing
+    /// whitespace is trimmed before comparing.
+    pub fn diff_ignore_whitespace<'a>(
+        &'a self,
+        other: &'a ArbFile,
+        cache: Option<&'a ArbFile>,
+    ) -> FileDiff {
+        self.diff_keys(other, cache, true)
+    }
+
+    fn diff_keys<'a>(
+        &'a self,
+        other: &'a ArbFile,
+        cache: Option<&'a ArbFile>,
+        ignore_whitespace: bool,
+    ) -> FileDiff {
         let lhs = self.contents.keys().collect::<HashSet<_>>();
         let rhs = other.contents.keys().collect::<HashSet<_>>();
         let create = lhs
@@ -113,12 +734,26 @@ impl ArbFile {
         let mut update = HashSet::new();
         if let Some(cache) = cache {
             for entry in cache.entries() {
-                if let (Some(current), Some(cached)) = (
-                    self.contents.get(entry.key().as_ref()),
-                    cache.contents.get(entry.key().as_ref()),
-                ) {
-                    if current != cached {
-                        update.insert(entry.key().as_ref().to_string());
+                let key = entry.key().as_ref();
+                if !entry.key().is_translatable() {
+                    continue;
+                }
+                if let (Some(current), Some(cached)) =
+                    (self.contents.get(key), cache.contents.get(key))
+                {
+                    // Descriptions are only compared for keys that opt in,
+                    // adding a description is a change when nothing
+                    // was recorded in the cache
+                    let description_changed = self.tracks_description(key)
+                        && self.description(key) != cache.description(key);
+                    let value_changed = match (current, cached) {
+                        (Value::String(current), Value::String(cached)) if ignore_whitespace => {
+                            !current.split_whitespace().eq(cached.split_whitespace())
+                        }
+                        _ => current != cached,
+                    };
+                    if value_changed || description_changed {
+                        update.insert(key.to_string());
                     }
                 }
             }
@@ -178,6 +813,28 @@ impl<'a> ArbKey<'a> {
         self.0.starts_with('@')
     }
 
+    /// Determine if this key declares meta data for another key.
+    ///
+    /// Global keys prefixed with @@ are not meta data for a key.
+    pub fn is_metadata(&self) -> bool {
+        self.is_prefixed() && !self.is_global_metadata()
+    }
+
+    /// Determine if this key is a global directive prefixed
+    /// with @@ such as `@@locale`.
+    pub fn is_global_metadata(&self) -> bool {
+        self.0.starts_with("@@")
+    }
+
+    /// Key described by this meta data key.
+    pub fn data_key(&self) -> Option<&'a str> {
+        if self.is_metadata() {
+            Some(&self.0[1..])
+        } else {
+            None
+        }
+    }
+
     /// Determine if this key is translatable.
     fn is_translatable(&self) -> bool {
         !self.is_prefixed()
@@ -215,6 +872,25 @@ impl<'a> ArbValue<'a> {
         }
     }
 
+    /// Parse the string into an ICU message tree.
+    ///
+    /// Values that are not strings parse to an empty message.
+    pub fn parse_icu(&self) -> Result<IcuMessage> {
+        match self.as_str() {
+            Some(source) => IcuMessage::parse(source),
+            None => Ok(IcuMessage::default()),
+        }
+    }
+
+    /// Placeholder names enclosed in curly braces in the string.
+    ///
+    /// Braces escaped using ICU apostrophe quoting such as
+    /// `'{name}'` are ignored; names are returned in order of
+    /// first appearance without duplicates.
+    pub fn placeholder_names(&self) -> Vec<String> {
+        self.as_str().map(placeholder_names).unwrap_or_default()
+    }
+
     /// Determine if this value is translatable.
     fn is_translatable(&self) -> bool {
         matches!(self.0, Value::String(_))
@@ -255,10 +931,18 @@ impl<'a> Placeholders<'a> {
 
     /// Verify that a source string contains all the referenced
     /// placeholders.
+    ///
+    /// The argument of plural and select messages counts as a
+    /// reference; sources that are not valid ICU messages must
+    /// contain `{name}` for each placeholder.
     pub fn verify(&self, source: &str) -> Result<()> {
+        let message = IcuMessage::parse(source).ok();
+        let names = message
+            .as_ref()
+            .map(|message| message.arguments())
+            .unwrap_or_default();
         for name in &self.0 {
-            let needle = format!("{{{}}}", name);
-            if !source.contains(&*needle) {
+            if !names.contains(name) && !source.contains(&format!("{{{}}}", name)) {
                 return Err(Error::PlaceholderNotDefined(
                     name.to_string(),
                     source.to_string(),
@@ -267,4 +951,74 @@ impl<'a> Placeholders<'a> {
         }
         Ok(())
     }
+
+    /// Verify the plural and select branches of a translation
+    /// against the branches of the source, including nested
+    /// messages.
+    ///
+    /// Branches are matched by selector and each placeholder
+    /// used in a source branch must be used in the matching
+    /// branch of the translation; `#` counts as a use of the
+    /// plural argument. Branches that only exist on one side
+    /// are ignored and when either string is not a valid ICU
+    /// message there is nothing to compare.
+    pub fn verify_translation(&self, source: &str, translation: &str) -> Result<()> {
+        let (Ok(expected), Ok(actual)) =
+            (IcuMessage::parse(source), IcuMessage::parse(translation))
+        else {
+            return Ok(());
+        };
+        self.verify_branches(&expected, &actual, translation)
+    }
+
+    fn verify_branches(
+        &self,
+        expected: &IcuMessage,
+        actual: &IcuMessage,
+        translation: &str,
+    ) -> Result<()> {
+        let mut translated = actual.nodes.iter().filter_map(|node| match node {
+            IcuNode::Branches {
+                argument, branches, ..
+            } => Some((argument, branches)),
+            _ => None,
+        });
+        for node in &expected.nodes {
+            let IcuNode::Branches {
+                argument,
+                kind,
+                branches,
+                ..
+            } = node
+            else {
+                continue;
+            };
+            let Some((_, other)) = translated.find(|(name, _)| *name == argument) else {
+                continue;
+            };
+            let uses = |message: &IcuMessage, name: &str| {
+                message.arguments().contains(&name)
+                    || (kind.is_plural() && name == argument && message.contains_pound())
+            };
+            for branch in branches {
+                let Some(target) = other
+                    .iter()
+                    .find(|target| target.selector == branch.selector)
+                else {
+                    continue;
+                };
+                for name in &self.0 {
+                    if uses(&branch.message, name) && !uses(&target.message, name) {
+                        return Err(Error::PlaceholderMissingInBranch(
+                            name.to_string(),
+                            branch.selector.to_string(),
+                            translation.to_string(),
+                        ));
+                    }
+                }
+                self.verify_branches(&branch.message, &target.message, translation)?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,75 @@
+const TAG: &str = "ent";
+
+/// Longest entity name that is recognized.
+const MAX_ENTITY: usize = 32;
+
+/// Name of the XML tag used to protect HTML entities.
+pub fn entity_tag() -> &'static str {
+    TAG
+}
+
+/// Protect HTML entities from translation.
+///
+/// Named (`&amp;`), decimal (`&#38;`) and hexadecimal (`&#x26;`)
+/// entities are replaced with numbered XML tags so they are not
+/// decoded or re-encoded by the API; the returned entities are
+/// used to restore the original text after translation.
+pub fn protect_entities(text: &str) -> (String, Vec<String>) {
+    let mut output = String::new();
+    let mut entities = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(length) = entity_length(rest) {
+            output.push_str(&format!("<{}>{}</{}>", TAG, entities.len(), TAG));
+            entities.push(rest[..length].to_string());
+            rest = &rest[length..];
+        } else {
+            output.push('&');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+    (output, entities)
+}
+
+/// Restore HTML entities after translation.
+pub fn restore_entities(text: &str, entities: &[String]) -> String {
+    restore_tagged(text, TAG, entities)
+}
+
+/// Restore segments protected with numbered XML tags.
+///
+/// The segment at each index replaces the first occurrence
+/// of `<tag>index</tag>` in the text.
+pub(crate) fn restore_tagged(text: &str, tag: &str, segments: &[String]) -> String {
+    let mut output = text.to_string();
+    for (index, segment) in segments.iter().enumerate() {
+        let needle = format!("<{}>{}</{}>", tag, index, tag);
+        output = output.replacen(&needle, segment, 1);
+    }
+    output
+}
+
+/// Length of the entity at the start of the text including
+/// the ampersand and semi-colon.
+fn entity_length(text: &str) -> Option<usize> {
+    let end = text[1..].find(';')? + 1;
+    if end > MAX_ENTITY {
+        return None;
+    }
+    let name = &text[1..end];
+    let valid = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(digits) = name.strip_prefix('#') {
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    } else {
+        name.chars()
+            .next()
+            .map(|c| c.is_ascii_alphabetic())
+            .unwrap_or(false)
+            && name.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    valid.then_some(end + 1)
+}
@@ -1,3 +1,4 @@
+use crate::SkipReason;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -8,6 +9,10 @@ pub enum Error {
     #[error("file '{0}' does not exist")]
     NoFile(PathBuf),
 
+    /// Template file does not exist.
+    #[error("template file '{0}' does not exist, check arb-dir and template-arb-file")]
+    TemplateNotFound(PathBuf),
+
     /// File does not exist.
     #[error("path '{0}' is not a file")]
     NotFile(PathBuf),
@@ -28,6 +33,11 @@ pub enum Error {
     #[error("template-arb-file is not defined in '{0}'")]
     TemplateArbFileNotDefined(PathBuf),
 
+    /// Localizations index file declares a `template-language`
+    /// that is not supported.
+    #[error("template-language '{0}' in '{1}' is not a supported language")]
+    InvalidTemplateLanguage(String, PathBuf),
+
     /// No YAML documents detected.
     #[error("no YAML documents in index file '{0}'")]
     NoYamlDocuments(PathBuf),
@@ -40,11 +50,69 @@ pub enum Error {
     #[error("key '{0}' is already prefixed with an @ symbol")]
     AlreadyPrefixed(String),
 
+    /// Global directive in the template has an invalid value.
+    #[error("invalid value for directive '{0}': {1}")]
+    InvalidDirective(String, String),
+
+    /// Key does not exist in the template or is not translatable.
+    #[error("key '{0}' is not a translatable key in the template")]
+    UnknownKey(String),
+
+    /// Key exists in the template but no translation was produced.
+    #[error("key '{0}' was not translated ({1})")]
+    KeySkipped(String, SkipReason),
+
     /// Placeholder defined in the JSON document does not exist
     /// in the string to be translated.
     #[error("placeholder '{0}' is declared but does not exist in source '{1}'")]
     PlaceholderNotDefined(String, String),
 
+    /// Error generated when a template file does not start with
+    /// the name of the template-arb-file.
+    #[error("template file '{0}' must start with the name of the template-arb-file")]
+    TemplatePartName(String),
+
+    /// Error generated in strict mode when an ARB file name
+    /// does not parse to a language.
+    #[error("file name '{0}' does not match a supported language")]
+    InvalidFileName(PathBuf),
+
+    /// Error generated when a placeholder used in a plural or
+    /// select branch of the source is missing from the same
+    /// branch of the translation.
+    #[error("placeholder '{0}' is missing from the '{1}' branch in '{2}'")]
+    PlaceholderMissingInBranch(String, String, String),
+
+    /// Error generated when a placeholder could not be
+    /// restored after translation.
+    #[error("placeholder '{0}' was lost translating '{1}'")]
+    PlaceholderLost(String, String),
+
+    /// Error generated when a string is not a valid ICU message.
+    #[error("invalid ICU message '{0}': {1}")]
+    InvalidIcuMessage(String, String),
+
+    /// Error generated when a markup type is invalid.
+    #[error("invalid markup '{0}'")]
+    InvalidMarkup(String),
+
+    /// Error generated when an output order is invalid.
+    #[error("invalid output order '{0}'")]
+    InvalidOutputOrder(String),
+
+    /// Error generated when a locale format is invalid.
+    #[error("invalid locale format '{0}'")]
+    InvalidLocaleFormat(String),
+
+    /// Error generated when a placeholder strategy is invalid.
+    #[error("invalid placeholder strategy '{0}'")]
+    InvalidPlaceholderStrategy(String),
+
+    /// Error generated when an application resource bundle
+    /// cannot be parsed.
+    #[error("invalid application resource bundle '{0}': {1}")]
+    InvalidArb(PathBuf, String),
+
     /// IO error.
     #[error(transparent)]
     Io(#[from] std::io::Error),
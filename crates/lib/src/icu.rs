@@ -0,0 +1,338 @@
+use crate::{Error, Result};
+
+/// Kind of a message with branches.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IcuKind {
+    /// Plural message.
+    Plural,
+    /// Ordinal plural message.
+    SelectOrdinal,
+    /// Select message.
+    Select,
+}
+
+impl IcuKind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "plural" => Some(Self::Plural),
+            "selectordinal" => Some(Self::SelectOrdinal),
+            "select" => Some(Self::Select),
+            _ => None,
+        }
+    }
+
+    /// Determine if `#` refers to the argument in branches.
+    pub fn is_plural(&self) -> bool {
+        !matches!(self, Self::Select)
+    }
+}
+
+/// Node in an ICU message.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum IcuNode {
+    /// Literal text.
+    Text(String),
+    /// Simple argument such as `{name}` or `{count, number}`.
+    Argument {
+        /// Argument name.
+        name: String,
+        /// Format type, for example `number` or `date`.
+        format: Option<String>,
+        /// Format style, for example `currency`.
+        style: Option<String>,
+    },
+    /// Number sign in a plural branch.
+    Pound,
+    /// Plural, ordinal or select message.
+    Branches {
+        /// Argument name.
+        argument: String,
+        /// Kind of message.
+        kind: IcuKind,
+        /// Plural offset.
+        offset: Option<u32>,
+        /// Branches in declaration order.
+        branches: Vec<IcuBranch>,
+    },
+}
+
+/// Branch of a plural, ordinal or select message.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IcuBranch {
+    /// Selector such as `one`, `=0` or `other`.
+    pub selector: String,
+    /// Message for the branch.
+    pub message: IcuMessage,
+}
+
+/// Message in ICU message syntax.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct IcuMessage {
+    /// Nodes in the message.
+    pub nodes: Vec<IcuNode>,
+}
+
+impl IcuMessage {
+    /// Parse a message.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+        };
+        let message = parser.message(false, false)?;
+        if parser.position < source.len() {
+            return Err(parser.error("unexpected '}'"));
+        }
+        Ok(message)
+    }
+
+    /// Names of all arguments in the message including
+    /// nested branches, without duplicates.
+    pub fn arguments(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_arguments(&mut names);
+        names
+    }
+
+    /// Determine if the message contains a number sign,
+    /// including nested branches.
+    pub fn contains_pound(&self) -> bool {
+        self.nodes.iter().any(|node| match node {
+            IcuNode::Pound => true,
+            IcuNode::Branches { branches, .. } => branches
+                .iter()
+                .any(|branch| branch.message.contains_pound()),
+            IcuNode::Text(_) | IcuNode::Argument { .. } => false,
+        })
+    }
+
+    fn collect_arguments<'a>(&'a self, names: &mut Vec<&'a str>) {
+        for node in &self.nodes {
+            match node {
+                IcuNode::Argument { name, .. } => {
+                    if !names.contains(&name.as_str()) {
+                        names.push(name);
+                    }
+                }
+                IcuNode::Branches {
+                    argument, branches, ..
+                } => {
+                    if !names.contains(&argument.as_str()) {
+                        names.push(argument);
+                    }
+                    for branch in branches {
+                        branch.message.collect_arguments(names);
+                    }
+                }
+                IcuNode::Text(_) | IcuNode::Pound => {}
+            }
+        }
+    }
+}
+
+/// Names of the arguments in a source string.
+///
+/// Strings that are not valid ICU messages have no names.
+pub(crate) fn placeholder_names(source: &str) -> Vec<String> {
+    IcuMessage::parse(source)
+        .map(|message| {
+            message
+                .arguments()
+                .into_iter()
+                .map(|name| name.to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: &str) -> Error {
+        Error::InvalidIcuMessage(
+            self.source.to_owned(),
+            format!("{} at offset {}", reason, self.position),
+        )
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Read until one of the delimiters without consuming it.
+    fn until(&mut self, delimiters: &[char]) -> &'a str {
+        let rest = &self.source[self.position..];
+        let end = rest.find(delimiters).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    /// Parse a message until the end of input or a closing
+    /// brace when nested.
+    fn message(&mut self, nested: bool, plural: bool) -> Result<IcuMessage> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => {
+                    if !text.is_empty() {
+                        nodes.push(IcuNode::Text(std::mem::take(&mut text)));
+                    }
+                    self.position += 1;
+                    nodes.push(self.argument(plural)?);
+                }
+                '}' => break,
+                '#' if plural => {
+                    if !text.is_empty() {
+                        nodes.push(IcuNode::Text(std::mem::take(&mut text)));
+                    }
+                    self.position += 1;
+                    nodes.push(IcuNode::Pound);
+                }
+                '\'' => self.quoted(&mut text, plural),
+                _ => {
+                    text.push(c);
+                    self.position += c.len_utf8();
+                }
+            }
+        }
+        if nested && self.peek().is_none() {
+            return Err(self.error("unclosed '{'"));
+        }
+        if !text.is_empty() {
+            nodes.push(IcuNode::Text(text));
+        }
+        Ok(IcuMessage { nodes })
+    }
+
+    /// Read apostrophe quoting into the text.
+    ///
+    /// A doubled apostrophe is a literal apostrophe and a single
+    /// apostrophe only starts quoted text before a special character.
+    fn quoted(&mut self, text: &mut String, plural: bool) {
+        self.position += 1;
+        match self.peek() {
+            Some('\'') => {
+                self.position += 1;
+                text.push('\'');
+                return;
+            }
+            Some('{' | '}' | '|') => {}
+            Some('#') if plural => {}
+            _ => {
+                text.push('\'');
+                return;
+            }
+        }
+        while let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+            if c == '\'' {
+                if self.peek() == Some('\'') {
+                    self.position += 1;
+                    text.push('\'');
+                } else {
+                    break;
+                }
+            } else {
+                text.push(c);
+            }
+        }
+    }
+
+    /// Parse an argument after the opening brace.
+    fn argument(&mut self, plural: bool) -> Result<IcuNode> {
+        let name = self.until(&[',', '}', '{']).trim();
+        let valid = !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && !name.starts_with(|c: char| c.is_numeric());
+        if !valid {
+            return Err(self.error("invalid argument name"));
+        }
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(IcuNode::Argument {
+                name: name.to_owned(),
+                format: None,
+                style: None,
+            });
+        }
+        self.expect(',')?;
+
+        let format = self.until(&[',', '}', '{']).trim();
+        if let Some(kind) = IcuKind::parse(format) {
+            self.expect(',')?;
+            return self.branches(name, kind, plural);
+        }
+        let style = if self.peek() == Some(',') {
+            self.position += 1;
+            Some(self.until(&['}', '{']).trim().to_owned())
+        } else {
+            None
+        };
+        self.expect('}')?;
+        Ok(IcuNode::Argument {
+            name: name.to_owned(),
+            format: Some(format.to_owned()),
+            style,
+        })
+    }
+
+    /// Parse the branches of a plural, ordinal or select message.
+    fn branches(&mut self, argument: &str, kind: IcuKind, plural: bool) -> Result<IcuNode> {
+        let mut offset = None;
+        let mut branches = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.position += 1;
+                    break;
+                }
+                None => return Err(self.error("unclosed '{'")),
+                _ => {}
+            }
+            let selector = self.until(&['{', '}', ' ', '\t', '\n', '\r']);
+            if let Some(value) = selector.strip_prefix("offset:") {
+                offset = Some(value.parse().map_err(|_| self.error("invalid offset"))?);
+                continue;
+            }
+            if selector.is_empty() {
+                return Err(self.error("missing selector"));
+            }
+            self.skip_whitespace();
+            self.expect('{')?;
+            let message = self.message(true, plural || kind.is_plural())?;
+            self.expect('}')?;
+            branches.push(IcuBranch {
+                selector: selector.to_owned(),
+                message,
+            });
+        }
+        if !branches.iter().any(|branch| branch.selector == "other") {
+            return Err(self.error("missing 'other' branch"));
+        }
+        Ok(IcuNode::Branches {
+            argument: argument.to_owned(),
+            kind,
+            offset,
+            branches,
+        })
+    }
+}
@@ -1,48 +1,172 @@
 use super::{Error, Result};
-use crate::{ArbEntry, ArbFile};
-use deepl::{DeeplApi, Lang, TagHandling, TranslateTextRequest};
+use crate::{
+    entity_tag, hash_source, icu::placeholder_names, markdown_tag, protect_entities,
+    protect_markdown, restore_entities, restore_markdown, ArbEntry, ArbFile, ArbKey, FileDiff,
+    Markup, Placeholders, ReviewEntry, TranslationProvider,
+};
+use deepl::{
+    CreateGlossaryRequest, DeeplApi, Formality, Glossary, Lang, SplitSentences, TagHandling,
+    TargetLang, TranslateTextRequest,
+};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex, MutexGuard},
 };
 use yaml_rust2::YamlLoader;
 
 const ARB_DIR: &str = "arb-dir";
 const TEMPLATE_ARB_FILE: &str = "template-arb-file";
+const TEMPLATE_ARB_FILES: &str = "template-arb-files";
+const TEMPLATE_LANGUAGE: &str = "template-language";
 const NAME_PREFIX: &str = "name-prefix";
 const OVERRIDES_DIR: &str = "overrides-dir";
+const CHARACTER_BUDGET: &str = "character-budget";
+const LOCALE_FORMAT: &str = "locale-format";
+const NAMESPACE_CONTEXT: &str = "namespace-context";
 const CACHE_FILE: &str = ".cache.json";
+const SENTINEL_START: char = '\u{e000}';
+const SENTINEL_END: char = '\u{e001}';
 
 /// Cache of template strings used for translations.
 ///
 /// Used to determine which keys need updating when strings
-/// in the template file are changed.
+/// in the template file are changed. The last translated
+/// values are also recorded so manual edits to generated
+/// files can be detected.
 #[derive(Debug, Default, Serialize, Deserialize)]
-pub struct ArbCache(BTreeMap<Lang, ArbFile>);
+pub struct ArbCache {
+    #[serde(
+        default,
+        rename = "@@translations",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    translations: BTreeMap<Lang, ArbFile>,
+    #[serde(flatten)]
+    files: BTreeMap<Lang, ArbFile>,
+}
 
 impl ArbCache {
     /// Get an application resource bundle file.
     pub fn get_file(&self, lang: &Lang) -> Option<&ArbFile> {
-        self.0.get(lang)
+        self.files.get(lang)
+    }
+
+    /// Get the last translated values for a language.
+    pub fn get_translations(&self, lang: &Lang) -> Option<&ArbFile> {
+        self.translations.get(lang)
+    }
+
+    /// Languages in the cache.
+    pub fn languages(&self) -> impl Iterator<Item = &Lang> {
+        self.files.keys()
+    }
+
+    /// Iterate the cached files for each language.
+    pub fn iter(&self) -> impl Iterator<Item = (&Lang, &ArbFile)> {
+        self.files.iter()
     }
 
     /// Add a cache entry.
     pub fn add_entry(&mut self, lang: Lang, entry: ArbEntry<'_>) {
-        let file = self.0.entry(lang).or_insert(ArbFile::default());
+        let file = self.files.entry(lang).or_default();
         file.insert_entry(entry);
     }
 
+    /// Record the description of a key so changes to the
+    /// description can be detected.
+    pub fn add_description(&mut self, lang: Lang, key: &ArbKey<'_>, description: Option<&str>) {
+        let file = self.files.entry(lang).or_default();
+        let meta_key = format!("@{}", key);
+        let mut meta = serde_json::Map::new();
+        if let Some(description) = description {
+            meta.insert(
+                "description".to_owned(),
+                Value::String(description.to_owned()),
+            );
+        }
+        let meta = Value::Object(meta);
+        file.insert_entry(ArbEntry::new(&meta_key, &meta));
+    }
+
+    /// Record the translated value for a key.
+    pub fn add_translation(&mut self, lang: Lang, key: &ArbKey<'_>, text: String) {
+        let file = self.translations.entry(lang).or_default();
+        file.insert_translation(key, text);
+    }
+
     /// Remove a cache entry.
     pub fn remove_entry(&mut self, lang: &Lang, key: &str) -> Option<Value> {
-        if let Some(file) = self.0.get_mut(lang) {
+        self.remove_translation(lang, key);
+        if let Some(file) = self.files.get_mut(lang) {
+            file.remove(&format!("@{}", key));
+            file.remove(key)
+        } else {
+            None
+        }
+    }
+
+    /// Remove the recorded translated value for a key.
+    pub fn remove_translation(&mut self, lang: &Lang, key: &str) -> Option<Value> {
+        if let Some(file) = self.translations.get_mut(lang) {
             file.remove(key)
         } else {
             None
         }
     }
+
+    /// Remove cache files for languages not in the given list.
+    ///
+    /// Returns the languages that were removed.
+    pub fn retain_languages(&mut self, languages: &[Lang]) -> Vec<Lang> {
+        let removed = self
+            .files
+            .keys()
+            .filter(|lang| !languages.contains(lang))
+            .copied()
+            .collect::<Vec<_>>();
+        for lang in &removed {
+            self.files.remove(lang);
+        }
+        self.translations.retain(|lang, _| languages.contains(lang));
+        removed
+    }
+
+    /// Merge another cache into this cache.
+    ///
+    /// Entries for each language are combined and entries in
+    /// the other cache replace existing entries with the same key.
+    pub fn merge(&mut self, other: ArbCache) {
+        merge_files(&mut self.files, other.files);
+        merge_files(&mut self.translations, other.translations);
+    }
+}
+
+fn merge_files(files: &mut BTreeMap<Lang, ArbFile>, other: BTreeMap<Lang, ArbFile>) {
+    for (lang, other) in other {
+        let file = files.entry(lang).or_default();
+        for entry in other.entries() {
+            file.insert_entry(entry);
+        }
+    }
+}
+
+/// Difference between the last translated value recorded
+/// in the cache and the value in a translation file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftEntry {
+    /// Key name.
+    pub key: String,
+    /// Value recorded when the key was last translated.
+    pub cached: String,
+    /// Current value in the translation file.
+    pub current: Option<String>,
 }
 
 /// Variants for key invalidation.
@@ -53,6 +177,243 @@ pub enum Invalidation {
     Keys(Vec<String>),
 }
 
+/// Variants for ordering the keys of a translation.
+///
+/// Meta data is always kept next to the key it describes.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum OutputOrder {
+    /// New keys are inserted at their position in the template.
+    #[default]
+    Template,
+    /// Keys are sorted alphabetically.
+    Sorted,
+    /// Existing keys keep their position and new keys
+    /// are appended.
+    Preserve,
+}
+
+impl AsRef<str> for OutputOrder {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Template => "template",
+            Self::Sorted => "sorted",
+            Self::Preserve => "preserve",
+        }
+    }
+}
+
+impl fmt::Display for OutputOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for OutputOrder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "template" => Self::Template,
+            "sorted" => Self::Sorted,
+            "preserve" => Self::Preserve,
+            _ => return Err(Error::InvalidOutputOrder(s.to_string())),
+        })
+    }
+}
+
+/// Variants for protecting placeholders from translation.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum PlaceholderStrategy {
+    /// Placeholders are converted to `<ph>` XML tags that
+    /// are ignored using the XML tag handling of the API.
+    #[default]
+    XmlTags,
+    /// Placeholders are converted to private use area
+    /// sentinels and tag handling is not required.
+    Sentinel,
+}
+
+impl PlaceholderStrategy {
+    /// Replace placeholders in the source text.
+    fn protect(&self, text: &str, names: &[&str]) -> String {
+        let mut text = text.to_owned();
+        for (index, name) in names.iter().enumerate() {
+            text = text.replacen(&format!("{{{}}}", name), &self.marker(index, name), 1);
+        }
+        text
+    }
+
+    /// Determine if every placeholder survived translation
+    /// exactly once and no stray markers were introduced.
+    fn matches(&self, translated: &str, names: &[&str]) -> bool {
+        match self {
+            Self::XmlTags => placeholder_tags_match(translated, names),
+            Self::Sentinel => {
+                translated.matches(SENTINEL_START).count() == names.len()
+                    && names
+                        .iter()
+                        .enumerate()
+                        .all(|(index, name)| translated.contains(&self.marker(index, name)))
+            }
+        }
+    }
+
+    /// Restore placeholders in the translated text.
+    fn restore(&self, translated: String, names: &[&str]) -> String {
+        let mut translated = translated;
+        for (index, name) in names.iter().enumerate() {
+            translated =
+                translated.replacen(&self.marker(index, name), &format!("{{{}}}", name), 1);
+        }
+        translated
+    }
+
+    fn marker(&self, index: usize, name: &str) -> String {
+        match self {
+            Self::XmlTags => format!("<ph>{}</ph>", name),
+            Self::Sentinel => format!("{}{}{}", SENTINEL_START, index, SENTINEL_END),
+        }
+    }
+}
+
+impl AsRef<str> for PlaceholderStrategy {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::XmlTags => "xml-tags",
+            Self::Sentinel => "sentinel",
+        }
+    }
+}
+
+impl fmt::Display for PlaceholderStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for PlaceholderStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "xml-tags" => Self::XmlTags,
+            "sentinel" => Self::Sentinel,
+            _ => return Err(Error::InvalidPlaceholderStrategy(s.to_string())),
+        })
+    }
+}
+
+/// Variants for rendering a language in file names.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum LocaleFormat {
+    /// Lowercase subtags delimited by underscores (`en_us`).
+    #[default]
+    Underscore,
+    /// Lowercase subtags delimited by hyphens (`en-us`).
+    Hyphen,
+    /// Underscores with an uppercase region (`en_US`).
+    UnderscoreRegion,
+    /// Hyphens with an uppercase region (`en-US`).
+    HyphenRegion,
+}
+
+impl LocaleFormat {
+    /// Separator between subtags.
+    pub fn separator(&self) -> char {
+        match self {
+            Self::Underscore | Self::UnderscoreRegion => '_',
+            Self::Hyphen | Self::HyphenRegion => '-',
+        }
+    }
+
+    /// Format a language code.
+    ///
+    /// Script subtags are title case when the region
+    /// is uppercase (`zh-Hans`).
+    pub fn format_code(&self, code: &str) -> String {
+        let region = matches!(self, Self::UnderscoreRegion | Self::HyphenRegion);
+        code.to_lowercase()
+            .split(['-', '_'])
+            .enumerate()
+            .map(|(index, subtag)| {
+                if !region || index == 0 {
+                    subtag.to_owned()
+                } else if subtag.len() == 4 {
+                    let (first, rest) = subtag.split_at(1);
+                    format!("{}{}", first.to_uppercase(), rest)
+                } else {
+                    subtag.to_uppercase()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&self.separator().to_string())
+    }
+}
+
+impl AsRef<str> for LocaleFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Underscore => "underscore",
+            Self::Hyphen => "hyphen",
+            Self::UnderscoreRegion => "underscore-region",
+            Self::HyphenRegion => "hyphen-region",
+        }
+    }
+}
+
+impl fmt::Display for LocaleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for LocaleFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "underscore" => Self::Underscore,
+            "hyphen" => Self::Hyphen,
+            "underscore-region" => Self::UnderscoreRegion,
+            "hyphen-region" => Self::HyphenRegion,
+            _ => return Err(Error::InvalidLocaleFormat(s.to_string())),
+        })
+    }
+}
+
+/// Variants for filtering meta data fields copied
+/// into translations.
+#[derive(Debug, Clone)]
+pub enum MetadataFilter {
+    /// Only copy these fields.
+    Allow(Vec<String>),
+    /// Copy all fields except these fields.
+    Deny(Vec<String>),
+}
+
+impl MetadataFilter {
+    /// Apply the filter to a meta data value.
+    ///
+    /// Values that are not objects are returned unchanged.
+    pub fn apply(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .filter(|(field, _)| match self {
+                        Self::Allow(fields) => fields.contains(field),
+                        Self::Deny(fields) => !fields.contains(field),
+                    })
+                    .map(|(field, value)| (field.to_owned(), value.clone()))
+                    .collect(),
+            ),
+            _ => value.clone(),
+        }
+    }
+}
+
+/// Function used to transform translated values.
+pub type PostProcess = Box<dyn Fn(&str) -> String + Send + Sync>;
+
 /// Options for translation.
 pub struct TranslationOptions {
     /// Target language.
@@ -63,6 +424,77 @@ pub struct TranslationOptions {
     pub invalidation: Option<Invalidation>,
     /// Overrides provided by humans.
     pub overrides: Option<HashMap<Lang, ArbFile>>,
+    /// Transform applied to each translated value before
+    /// it is inserted into the output.
+    pub post_process: Option<PostProcess>,
+    /// Glossary identifier.
+    ///
+    /// When a glossary is used the source language is set
+    /// to the template language.
+    pub glossary_id: Option<String>,
+    /// Context applied to every translation.
+    ///
+    /// A context declared in the meta data for a key takes
+    /// precedence over the global context.
+    pub global_context: Option<String>,
+    /// Markup handling for source strings.
+    pub markup: Markup,
+    /// Skip keys that fail placeholder verification and collect
+    /// the errors in the result rather than aborting.
+    pub collect_errors: bool,
+    /// Raw language code sent to the API in place of the target
+    /// language and used to name the output file.
+    ///
+    /// Allows translating to languages supported by DeepL that
+    /// have not been added to [Lang] yet; when set the target
+    /// language is ignored. The cache and overrides are keyed
+    /// by [Lang] so they are not used for raw codes.
+    pub target_code: Option<String>,
+    /// Formality for the translations.
+    ///
+    /// When the target language does not support formality
+    /// the request is retried without formality.
+    pub formality: Option<Formality>,
+    /// Store a hash of the source value in the meta data for
+    /// each translated key.
+    ///
+    /// Changes to source values are then detected from the
+    /// target file without relying on the cache.
+    pub source_hash: bool,
+    /// Filter for the fields in key meta data copied into
+    /// the translation, by default all fields are copied.
+    pub metadata_filter: Option<MetadataFilter>,
+    /// Maximum number of characters to send for the language.
+    ///
+    /// Takes precedence over the `character-budget` declared
+    /// in the index file; keys that would exceed the budget are
+    /// deferred to a later run.
+    pub character_budget: Option<usize>,
+    /// Keep the existing value for a key when the translation
+    /// is empty or only whitespace.
+    pub keep_on_empty: bool,
+    /// Retry once with formatting preserved and sentence
+    /// splitting disabled when placeholders are lost after
+    /// translating without tag handling.
+    pub retry_preserve_formatting: bool,
+    /// Order of the keys in the translation.
+    pub output_order: OutputOrder,
+    /// Strategy used to protect placeholders.
+    pub placeholder_strategy: PlaceholderStrategy,
+    /// Do not update keys when the source text only differs
+    /// from the cached value by whitespace.
+    pub ignore_whitespace: bool,
+    /// Template used to detect updated keys instead of the cache.
+    ///
+    /// Allows translating the keys that changed since a revision
+    /// of the template, for example a file written by `git show`.
+    pub baseline: Option<ArbFile>,
+    /// Existing translation to update instead of the file
+    /// for the language.
+    ///
+    /// Used when the translation is written to another file
+    /// so that changes are detected against that file.
+    pub existing: Option<ArbFile>,
     /// Disable updating the cache.
     ///
     /// Used in the test specs, you probably don't want
@@ -79,6 +511,23 @@ impl TranslationOptions {
             dry_run: false,
             invalidation: None,
             overrides: None,
+            post_process: None,
+            glossary_id: None,
+            global_context: None,
+            markup: Default::default(),
+            collect_errors: false,
+            target_code: None,
+            formality: None,
+            source_hash: false,
+            metadata_filter: None,
+            character_budget: None,
+            keep_on_empty: true,
+            retry_preserve_formatting: true,
+            output_order: Default::default(),
+            placeholder_strategy: Default::default(),
+            ignore_whitespace: false,
+            baseline: None,
+            existing: None,
             disable_cache: false,
         }
     }
@@ -90,9 +539,186 @@ pub struct TranslateResult {
     /// Template information.
     pub template: ArbFile,
     /// Translated content.
+    ///
+    /// For a dry run keys that would be translated contain
+    /// the source text.
     pub translated: ArbFile,
-    /// Number of translations.
+    /// Number of translated keys.
+    ///
+    /// Keys with identical source text share a single
+    /// translation so the number of strings sent to the
+    /// API may be smaller.
     pub length: usize,
+    /// Whether the translated content differs from the
+    /// existing file.
+    pub changed: bool,
+    /// Errors for keys that were skipped when collecting errors.
+    pub errors: Vec<(String, Error)>,
+    /// Keys replaced by human-provided overrides.
+    pub overridden: Vec<String>,
+    /// Keys deleted because they were removed from the template.
+    pub deleted: Vec<String>,
+    /// Number of characters sent to the API.
+    pub characters: usize,
+    /// Keys not translated because the character budget
+    /// was exhausted.
+    pub deferred: Vec<String>,
+    /// Translatable keys that were not translated and why.
+    pub skipped: Vec<SkippedKey>,
+    /// Warnings generated during the translation.
+    pub warnings: Vec<Warning>,
+    /// Origin of the value for each translatable key in
+    /// the translated content.
+    pub provenance: BTreeMap<String, KeyProvenance>,
+    /// Diff used to plan the translation.
+    pub diff: FileDiff,
+    /// Whether the cache was updated.
+    ///
+    /// When writing the cache is deferred this determines if
+    /// the cache needs to be flushed.
+    pub cache_changed: bool,
+}
+
+/// Reason a key was not translated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// Replaced by a human-provided override.
+    Overridden,
+    /// Key has not been created or updated in the template.
+    NotInDiff,
+    /// Key is locked in the template meta data.
+    Locked,
+    /// Key is excluded from translation in the template meta data.
+    Excluded,
+    /// Source text would exceed the character budget for
+    /// the language so the key is deferred to a later run.
+    TooLong,
+    /// Key failed validation when collecting errors.
+    Invalid,
+    /// Translation was empty so the existing value was kept.
+    Empty,
+}
+
+impl AsRef<str> for SkipReason {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Overridden => "overridden",
+            Self::NotInDiff => "not-in-diff",
+            Self::Locked => "locked",
+            Self::Excluded => "excluded",
+            Self::TooLong => "too-long",
+            Self::Invalid => "invalid",
+            Self::Empty => "empty",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// Origin of a translated value.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Provenance {
+    /// Translated by the API in this run.
+    Machine,
+    /// Replaced by a human-provided override.
+    Override,
+    /// Existing value that did not need translating.
+    Cached,
+    /// Source text copied for an excluded key.
+    Excluded,
+}
+
+/// Provenance of the value for a key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyProvenance {
+    /// Origin of the value.
+    pub provenance: Provenance,
+    /// Number of characters in the value.
+    pub characters: usize,
+    /// Source language detected by the API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_source: Option<Lang>,
+}
+
+/// Warning generated during a translation.
+///
+/// Warnings are also logged; they are collected so tooling
+/// can report them without parsing log output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Warning {
+    /// Placeholder tags did not survive translation so the
+    /// key was translated again without tag handling.
+    PlaceholderFallback {
+        /// Key name.
+        key: String,
+    },
+    /// Detected source language differs from the template language.
+    SourceMismatch {
+        /// Key name.
+        key: String,
+        /// Language detected by the API.
+        detected: Lang,
+    },
+    /// Source text for a locked key has changed.
+    LockedStale {
+        /// Key name.
+        key: String,
+    },
+    /// Formality is not supported for the target language.
+    FormalityUnsupported {
+        /// Target language.
+        lang: String,
+    },
+    /// Response contained fewer translations than requested.
+    PartialResponse {
+        /// Number of texts sent.
+        expected: usize,
+        /// Number of translations received.
+        received: usize,
+    },
+    /// ARB file that was ignored because the file name does
+    /// not parse to a language.
+    SkippedFile {
+        /// File path.
+        path: PathBuf,
+    },
+    /// Translation was empty so the existing value was kept.
+    EmptyTranslation {
+        /// Key name.
+        key: String,
+    },
+    /// Character usage is close to the account limit.
+    QuotaProximity {
+        /// Characters used.
+        used: u64,
+        /// Character limit.
+        limit: u64,
+    },
+}
+
+/// Key that was not translated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedKey {
+    /// Key name.
+    pub key: String,
+    /// Reason the key was skipped.
+    pub reason: SkipReason,
+}
+
+impl SkippedKey {
+    fn new(key: &ArbKey<'_>, reason: SkipReason) -> Self {
+        Self {
+            key: key.to_string(),
+            reason,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -109,6 +735,19 @@ enum CachedEntry<'a> {
         names: Option<Vec<&'a str>>,
         /// Specific index to insert.
         index: Option<usize>,
+        /// Index of the request batch.
+        batch: usize,
+        /// Position of the source text in the request.
+        position: usize,
+        /// Protected Markdown segments.
+        segments: Vec<String>,
+        /// Protected HTML entities.
+        entities: Vec<String>,
+        /// Source text with Markdown and entities protected
+        /// used when retrying with sentinels.
+        protected: String,
+        /// Leading and trailing whitespace of the source text.
+        padding: (String, String),
     },
 }
 
@@ -120,15 +759,27 @@ enum CachedEntry<'a> {
 /// by an underscore. Language identifiers in file names should use
 /// underscores and ***not hyphens***. For example, the file name for
 /// the `EN-US` language would be `app_en_us.arb`.
+///
+/// The cache is guarded by a mutex so translations for different
+/// languages may run concurrently using a shared reference; each
+/// translation only modifies the cache entries for the target
+/// language and the cache file is written whilst holding the lock.
 #[derive(Debug)]
 pub struct Intl {
     file_path: PathBuf,
     arb_dir: String,
     template_language: Lang,
     template_arb_file: String,
+    template_parts: Vec<String>,
     name_prefix: String,
     overrides_dir: Option<String>,
-    pub(crate) cache: ArbCache,
+    character_budgets: HashMap<Lang, usize>,
+    namespace_contexts: BTreeMap<String, String>,
+    locale_format: LocaleFormat,
+    output_dir: Option<PathBuf>,
+    strict: bool,
+    defer_cache: bool,
+    pub(crate) cache: Mutex<ArbCache>,
 }
 
 impl Intl {
@@ -171,21 +822,75 @@ impl Intl {
 
         let overrides_dir = doc[OVERRIDES_DIR].as_str().map(|s| s.to_string());
 
-        let stem = template_arb_file.trim_end_matches(".arb");
-        let pat = format!("{}_", name_prefix);
-        let lang_code = stem.trim_start_matches(&pat);
-        let template_language: Lang = lang_code.parse()?;
+        let mut character_budgets = HashMap::new();
+        if let Some(budgets) = doc[CHARACTER_BUDGET].as_hash() {
+            for (lang, budget) in budgets {
+                if let (Some(lang), Some(budget)) = (lang.as_str(), budget.as_i64()) {
+                    let lang: Lang = lang.replace('_', "-").parse()?;
+                    character_budgets.insert(lang, budget.max(0) as usize);
+                }
+            }
+        }
+
+        let mut namespace_contexts = BTreeMap::new();
+        if let Some(contexts) = doc[NAMESPACE_CONTEXT].as_hash() {
+            for (namespace, context) in contexts {
+                if let (Some(namespace), Some(context)) = (namespace.as_str(), context.as_str()) {
+                    namespace_contexts.insert(namespace.to_owned(), context.to_owned());
+                }
+            }
+        }
+
+        let locale_format = if let Some(format) = doc[LOCALE_FORMAT].as_str() {
+            format.parse()?
+        } else {
+            LocaleFormat::default()
+        };
+
+        let template_patterns = if let Some(files) = doc[TEMPLATE_ARB_FILES].as_vec() {
+            files
+                .iter()
+                .filter_map(|file| file.as_str().map(|s| s.to_string()))
+                .collect()
+        } else if let Some(file) = doc[TEMPLATE_ARB_FILES].as_str() {
+            vec![file.to_string()]
+        } else {
+            Vec::new()
+        };
+
+        let template_language: Lang = if let Some(code) = doc[TEMPLATE_LANGUAGE].as_str() {
+            // Explicit language takes precedence over the file name
+            code.replace('_', "-").parse().map_err(|_| {
+                Error::InvalidTemplateLanguage(code.to_owned(), path.as_ref().to_owned())
+            })?
+        } else {
+            let stem = template_arb_file.trim_end_matches(".arb");
+            let pat = format!("{}_", name_prefix);
+            let lang_code = stem.trim_start_matches(&pat);
+            // Allow hyphens so projects can be normalized
+            lang_code.replace('_', "-").to_lowercase().parse()?
+        };
 
         let mut index = Intl {
             file_path: path.as_ref().to_owned(),
             arb_dir: arb_dir.to_owned(),
             template_arb_file: template_arb_file.to_owned(),
+            template_parts: Vec::new(),
             template_language,
             name_prefix,
             cache: Default::default(),
             overrides_dir,
+            character_budgets,
+            namespace_contexts,
+            locale_format,
+            output_dir: None,
+            strict: false,
+            defer_cache: false,
         };
-        index.cache = index.read_cache()?;
+        if !template_patterns.is_empty() {
+            index.template_parts = index.resolve_template_parts(&template_patterns)?;
+        }
+        index.cache = Mutex::new(index.read_cache()?);
 
         Ok(index)
     }
@@ -210,26 +915,213 @@ impl Intl {
         self.overrides_dir.as_ref().map(|s| &s[..])
     }
 
+    /// Character budget declared in the index file for a language.
+    pub fn character_budget(&self, lang: &Lang) -> Option<usize> {
+        self.character_budgets.get(lang).copied()
+    }
+
+    /// Set a directory for translation output.
+    ///
+    /// When set the cache file is written to this directory
+    /// instead of the `arb-dir`.
+    pub fn set_output_dir(&mut self, dir: Option<PathBuf>) {
+        self.output_dir = dir;
+    }
+
+    /// Context declared in the index file for the namespace
+    /// of a dotted key.
+    ///
+    /// The longest namespace that prefixes the key is used so
+    /// `settings.privacy` takes precedence over `settings` for
+    /// the key `settings.privacy.title`.
+    pub fn namespace_context(&self, key: &str) -> Option<&str> {
+        self.namespace_contexts
+            .iter()
+            .filter(|(namespace, _)| {
+                key.strip_prefix(namespace.as_str())
+                    .map(|rest| rest.starts_with('.'))
+                    .unwrap_or(false)
+            })
+            .max_by_key(|(namespace, _)| namespace.len())
+            .map(|(_, context)| context.as_str())
+    }
+
+    /// Set the format of languages in file names.
+    ///
+    /// Overrides the `locale-format` declared in the index file.
+    pub fn set_locale_format(&mut self, format: LocaleFormat) {
+        self.locale_format = format;
+    }
+
+    /// Format of languages in file names.
+    pub fn locale_format(&self) -> LocaleFormat {
+        self.locale_format
+    }
+
+    /// Set strict mode for listing directories.
+    ///
+    /// When enabled files with an `.arb` extension that do
+    /// not parse to a language are an error.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Defer writing the cache file.
+    ///
+    /// When enabled translations only update the cache in memory,
+    /// call [Intl::flush_cache] to write the cache once all
+    /// translations have succeeded.
+    pub fn set_defer_cache(&mut self, defer: bool) {
+        self.defer_cache = defer;
+    }
+
+    /// Write the in-memory cache to disc.
+    pub fn flush_cache(&self) -> Result<()> {
+        self.write_cache()
+    }
+
+    /// Directory for translation output.
+    ///
+    /// Defaults to the application resource bundle directory.
+    pub fn output_directory(&self) -> Result<PathBuf> {
+        if let Some(dir) = &self.output_dir {
+            Ok(dir.to_owned())
+        } else {
+            self.arb_directory()
+        }
+    }
+
     /// Language of the template application resource bundle.
     pub fn template_language(&self) -> &Lang {
         &self.template_language
     }
 
     /// Get the cache of original translations.
-    pub fn cache(&self) -> &ArbCache {
-        &self.cache
+    ///
+    /// The cache is locked until the returned guard is dropped.
+    pub fn cache(&self) -> MutexGuard<'_, ArbCache> {
+        // Cache updates are never left partially applied so it
+        // is safe to recover from a poisoned lock
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Load and parse the template application resource bundle.
+    ///
+    /// When template parts are configured they are merged
+    /// into the template.
     pub fn template_content(&self) -> Result<ArbFile> {
-        let path = self
+        let path = self.template_path()?;
+        if !path.try_exists()? {
+            return Err(Error::TemplateNotFound(path));
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let mut template = parse_arb(&path, &content)?;
+        for part in &self.template_parts {
+            template.extend(self.load_file(self.arb_directory()?.join(part))?);
+        }
+        Ok(template)
+    }
+
+    /// Path to the template application resource bundle.
+    pub fn template_path(&self) -> Result<PathBuf> {
+        Ok(self
             .parent_path()?
             .to_owned()
             .join(&self.arb_dir)
-            .join(&self.template_arb_file);
+            .join(&self.template_arb_file))
+    }
 
-        let content = std::fs::read_to_string(&path)?;
-        Ok(serde_json::from_str(&content)?)
+    /// Additional template files merged into the template.
+    ///
+    /// Declared using `template-arb-files` which may contain
+    /// file names or patterns using `*` relative to the `arb-dir`.
+    pub fn template_parts(&self) -> &[String] {
+        &self.template_parts
+    }
+
+    /// Resolve template file patterns to file names.
+    fn resolve_template_parts(&self, patterns: &[String]) -> Result<Vec<String>> {
+        let mut names = std::fs::read_dir(self.arb_directory()?)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        let mut parts = Vec::new();
+        for pattern in patterns {
+            for name in &names {
+                if name != &self.template_arb_file
+                    && !parts.contains(name)
+                    && wildcard_match(pattern, name)
+                {
+                    // Must be able to derive names for other languages
+                    self.part_suffix(name)?;
+                    parts.push(name.to_owned());
+                }
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Suffix of a template part after the template file stem.
+    fn part_suffix<'a>(&self, part: &'a str) -> Result<&'a str> {
+        let stem = self.template_arb_file.trim_end_matches(".arb");
+        part.strip_prefix(stem)
+            .filter(|suffix| suffix.starts_with('_'))
+            .ok_or_else(|| Error::TemplatePartName(part.to_owned()))
+    }
+
+    /// File name of a template part for a language code.
+    fn part_file_name(&self, part: &str, code: &str) -> Result<String> {
+        let suffix = self.part_suffix(part)?;
+        let name = self.format_file_name_code(code);
+        Ok(format!("{}{}", name.trim_end_matches(".arb"), suffix))
+    }
+
+    /// Whether a path is a template part for a language.
+    fn is_template_part(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().map(|s| s.to_string_lossy()) else {
+            return false;
+        };
+        self.template_parts.iter().any(|part| {
+            self.part_suffix(part)
+                .ok()
+                .and_then(|suffix| name.strip_suffix(suffix))
+                .map(|main| self.parse_file_name(format!("{}.arb", main)).is_some())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Split a translation into the main file and a file for
+    /// each template part.
+    ///
+    /// Paths are in the output directory; when no template parts
+    /// are configured only the main file is returned.
+    pub fn split_translation(
+        &self,
+        code: &str,
+        translated: &ArbFile,
+    ) -> Result<Vec<(PathBuf, ArbFile)>> {
+        let dir = self.output_directory()?;
+        let mut main = translated.clone();
+        let mut output = Vec::new();
+        for part in &self.template_parts {
+            let keys = self.load_file(self.arb_directory()?.join(part))?;
+            let file = main.split_off(&keys);
+            output.push((dir.join(self.part_file_name(part, code)?), file));
+        }
+        output.insert(0, (dir.join(self.format_file_name_code(code)), main));
+        Ok(output)
+    }
+
+    /// Merge the template parts for a language code into a file.
+    fn merge_parts(&self, code: &str, mut file: ArbFile) -> Result<ArbFile> {
+        for part in &self.template_parts {
+            let path = self.arb_directory()?.join(self.part_file_name(part, code)?);
+            file.extend(self.load_path_or_default(path)?);
+        }
+        Ok(file)
     }
 
     /// Compute the parent of the index file.
@@ -241,58 +1133,105 @@ impl Intl {
 
     /// Path to a language file.
     pub fn file_path(&self, lang: Lang) -> Result<PathBuf> {
-        Ok(self.arb_directory()?.join(self.format_file_name(lang)))
+        self.file_path_code(&lang.to_string())
+    }
+
+    /// Path to a language file for a raw language code.
+    pub fn file_path_code(&self, code: &str) -> Result<PathBuf> {
+        Ok(self.arb_directory()?.join(self.format_file_name_code(code)))
     }
 
     /// Format a language to a file name.
     pub fn format_file_name(&self, lang: Lang) -> String {
+        self.format_file_name_code(&lang.to_string())
+    }
+
+    /// Format a raw language code to a file name.
+    pub fn format_file_name_code(&self, code: &str) -> String {
         format!(
             "{}_{}.arb",
             self.name_prefix,
-            lang.to_string().to_lowercase().replace("-", "_")
+            self.locale_format.format_code(code)
         )
     }
 
     /// Parse a file path to a language.
+    ///
+    /// Language identifiers must be delimited by the separator
+    /// of the locale format, file names that use another
+    /// separator are not matched.
     pub fn parse_file_name(&self, path: impl AsRef<Path>) -> Option<Lang> {
-        if let Some(name) = path.as_ref().file_stem() {
-            let name = name.to_string_lossy();
-            if name.starts_with(&self.name_prefix) {
-                let pat = format!("{}_", self.name_prefix);
-                let lang_code = name.trim_start_matches(&pat);
-                lang_code.parse().ok()
-            } else {
-                None
-            }
-        } else {
-            None
+        let other = match self.locale_format.separator() {
+            '_' => '-',
+            _ => '_',
+        };
+        self.file_name_lang_code(path.as_ref())
+            .filter(|code| !code.contains(other))
+            .and_then(|code| code.replace('_', "-").to_lowercase().parse().ok())
+    }
+
+    /// Parse a file path that uses hyphens in the language
+    /// identifier to a language.
+    ///
+    /// Always `None` when the locale format uses hyphens.
+    pub fn parse_hyphenated_file_name(&self, path: impl AsRef<Path>) -> Option<Lang> {
+        if self.locale_format.separator() == '-' {
+            return None;
         }
+        self.file_name_lang_code(path.as_ref())
+            .filter(|code| code.contains('-'))
+            .and_then(|code| code.replace('_', "-").to_lowercase().parse().ok())
+    }
+
+    fn file_name_lang_code(&self, path: &Path) -> Option<String> {
+        let name = path.file_stem()?.to_string_lossy();
+        let pat = format!("{}_", self.name_prefix);
+        name.strip_prefix(&pat).map(|code| code.to_owned())
     }
 
     /// Compute the application resource bundle directory relative to the
     /// parent of the internationalization index file.
     pub fn arb_directory(&self) -> Result<PathBuf> {
-        let arb_dir = PathBuf::from(&self.arb_dir);
-        let parent = if arb_dir.is_relative() {
-            self.parent_path()?.join(arb_dir)
-        } else {
-            arb_dir
-        };
-
+        let parent = self.arb_path()?;
         if !parent.is_dir() {
             return Err(Error::NotDirectory(parent));
         }
-
         Ok(parent)
     }
 
-    /// List translations in the configured `arb-dir`.
-    pub fn list_translated(&self) -> Result<BTreeMap<Lang, PathBuf>> {
-        self.list_directory(self.arb_directory()?)
+    /// Create the directory for translation output when it
+    /// does not exist yet.
+    ///
+    /// Returns the output directory.
+    pub fn ensure_output_directory(&self) -> Result<PathBuf> {
+        let dir = match &self.output_dir {
+            Some(dir) => dir.to_owned(),
+            None => self.arb_path()?,
+        };
+        if !dir.is_dir() {
+            tracing::info!(path = %dir.display(), "create directory");
+            std::fs::create_dir_all(&dir)?;
+        }
+        Ok(dir)
     }
 
-    /// List translated languages in a directory.
-    pub fn list_directory(&self, dir: impl AsRef<Path>) -> Result<BTreeMap<Lang, PathBuf>> {
+    /// Path to the `arb-dir` which may not exist.
+    fn arb_path(&self) -> Result<PathBuf> {
+        let arb_dir = PathBuf::from(&self.arb_dir);
+        Ok(if arb_dir.is_relative() {
+            self.parent_path()?.join(arb_dir)
+        } else {
+            arb_dir
+        })
+    }
+
+    /// List translations in the configured `arb-dir`.
+    pub fn list_translated(&self) -> Result<BTreeMap<Lang, PathBuf>> {
+        self.list_directory(self.arb_directory()?)
+    }
+
+    /// List translated languages in a directory.
+    pub fn list_directory(&self, dir: impl AsRef<Path>) -> Result<BTreeMap<Lang, PathBuf>> {
         let mut output = BTreeMap::new();
 
         if !dir.as_ref().is_dir() {
@@ -302,13 +1241,110 @@ impl Intl {
         for entry in std::fs::read_dir(dir.as_ref())? {
             let entry = entry?;
             let path = entry.path();
-            if let (true, Some(lang)) = (path.is_file(), self.parse_file_name(&path)) {
+            if !path.is_file() {
+                tracing::trace!(path = %path.display(), "skip, not a file");
+                continue;
+            }
+
+            if let Some(lang) = self.parse_file_name(&path) {
                 output.insert(lang, path);
+                continue;
+            }
+
+            if self.is_template_part(&path) {
+                tracing::trace!(path = %path.display(), "skip, template part");
+                continue;
+            }
+
+            let is_arb = path.extension().map(|ext| ext == "arb").unwrap_or(false);
+            if is_arb && self.strict {
+                return Err(Error::InvalidFileName(path));
+            }
+
+            if let Some(lang) = self.parse_hyphenated_file_name(&path) {
+                tracing::warn!(
+                    path = %path.display(),
+                    expected = %self.format_file_name(lang),
+                    "ignoring hyphenated file name, run normalize to rename");
+            } else if self.file_name_lang_code(&path).is_none() {
+                tracing::debug!(
+                    path = %path.display(),
+                    prefix = %self.name_prefix,
+                    "skip, file name prefix does not match");
+            } else {
+                tracing::debug!(
+                    path = %path.display(),
+                    "skip, file name is not a supported language");
             }
         }
         Ok(output)
     }
 
+    /// ARB files in the `arb-dir` that are ignored because the
+    /// file name does not parse to a language.
+    pub fn skipped_files(&self) -> Result<Vec<PathBuf>> {
+        let mut skipped = Vec::new();
+        for entry in std::fs::read_dir(self.arb_directory()?)? {
+            let path = entry?.path();
+            let is_arb = path.extension().map(|ext| ext == "arb").unwrap_or(false);
+            let is_template = path.file_name() == Some(self.template_arb_file.as_ref());
+            if path.is_file()
+                && is_arb
+                && !is_template
+                && self.parse_file_name(&path).is_none()
+                && !self.is_template_part(&path)
+            {
+                skipped.push(path);
+            }
+        }
+        skipped.sort();
+        Ok(skipped)
+    }
+
+    /// Rename files in the `arb-dir` that use hyphens in the
+    /// language identifier to use underscores.
+    ///
+    /// When the template file is renamed the reference in the
+    /// index file is also updated.
+    ///
+    /// Returns the source and destination paths for each file.
+    pub fn normalize_file_names(&self, dry_run: bool) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let dir = self.arb_directory()?;
+        let mut renames = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if let (true, Some(lang)) = (path.is_file(), self.parse_hyphenated_file_name(&path)) {
+                let target = dir.join(self.format_file_name(lang));
+                if target.try_exists()? {
+                    tracing::warn!(
+                        path = %path.display(),
+                        target = %target.display(),
+                        "skip normalize, target exists");
+                    continue;
+                }
+                renames.push((path, target));
+            }
+        }
+
+        if !dry_run {
+            for (from, to) in &renames {
+                tracing::info!(from = %from.display(), to = %to.display(), "rename");
+                std::fs::rename(from, to)?;
+
+                let from_name = from.file_name().map(|s| s.to_string_lossy());
+                if from_name.as_deref() == Some(self.template_arb_file.as_str()) {
+                    let to_name = to.file_name().unwrap_or_default().to_string_lossy();
+                    let content = std::fs::read_to_string(&self.file_path)?;
+                    let content = content.replacen(&self.template_arb_file, &to_name, 1);
+                    tracing::info!(path = %self.file_path.display(), "write file");
+                    std::fs::write(&self.file_path, content)?;
+                }
+            }
+        }
+
+        Ok(renames)
+    }
+
     /// Attempt to load override definitions.
     ///
     /// If a languages list is given only load the
@@ -327,7 +1363,7 @@ impl Intl {
                 }
             }
             let content = std::fs::read_to_string(&path)?;
-            let file: ArbFile = serde_json::from_str(&content)?;
+            let file = parse_arb(&path, &content)?;
             output.insert(lang, file);
         }
         Ok(output)
@@ -335,18 +1371,39 @@ impl Intl {
 
     /// Load a language file from disc.
     pub fn load(&self, lang: Lang) -> Result<ArbFile> {
-        let path = self.file_path(lang)?;
-        if !path.try_exists()? {
-            return Err(Error::NoFile(path));
-        }
-        let content = std::fs::read_to_string(&path)?;
-        Ok(serde_json::from_str(&content)?)
+        let file = self.load_file(self.file_path(lang)?)?;
+        self.merge_parts(&lang.to_string(), file)
     }
 
     /// Load a language file if it exists otherwise use an
     /// empty file.
     pub fn load_or_default(&self, lang: Lang) -> Result<ArbFile> {
-        match self.load(lang) {
+        let file = self.load_path_or_default(self.file_path(lang)?)?;
+        self.merge_parts(&lang.to_string(), file)
+    }
+
+    /// Export a review of a translation.
+    ///
+    /// Each translatable key maps to the source text, the
+    /// translated text and the description from the template.
+    pub fn export_review(&self, lang: Lang) -> Result<IndexMap<String, ReviewEntry>> {
+        let template = self.template_content()?;
+        let translated = self.load(lang)?;
+        Ok(template.review(&translated))
+    }
+
+    /// Load an application resource bundle from a path.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<ArbFile> {
+        let path = path.as_ref();
+        if !path.try_exists()? {
+            return Err(Error::NoFile(path.to_path_buf()));
+        }
+        let content = std::fs::read_to_string(path)?;
+        parse_arb(path, &content)
+    }
+
+    fn load_path_or_default(&self, path: PathBuf) -> Result<ArbFile> {
+        match self.load_file(path) {
             Ok(res) => Ok(res),
             Err(Error::NoFile(_)) => Ok(ArbFile::default()),
             Err(e) => Err(e),
@@ -357,21 +1414,165 @@ impl Intl {
     ///
     /// Placeholders are converted to XML tags and ignored from
     /// translation to preserve the placeholder names.
+    ///
+    /// When the target language is the template language the
+    /// template is passed through without calling the API.
+    ///
+    /// Formality and context declared with `@@x-arb-` directives
+    /// in the template are used when not set in the options.
     pub async fn translate(
-        &mut self,
-        api: &DeeplApi,
-        options: TranslationOptions,
+        &self,
+        api: &impl TranslationProvider,
+        mut options: TranslationOptions,
     ) -> Result<TranslateResult> {
-        tracing::info!(lang = %options.target_lang, "translate");
+        let target: TargetLang = if let Some(code) = &options.target_code {
+            TargetLang::Code(code.to_owned())
+        } else {
+            options.target_lang.into()
+        };
+        // Cache and overrides are keyed by language
+        let lang = if options.target_code.is_none() {
+            Some(options.target_lang)
+        } else {
+            None
+        };
+
+        tracing::info!(lang = %target, "translate");
 
         let template = self.template_content()?;
-        let mut output = self.load_or_default(options.target_lang)?;
+
+        // Directives in the template seed options that were not set
+        let config = template.tool_config()?;
+        if options.formality.is_none() {
+            options.formality = config.formality;
+        }
+        if options.global_context.is_none() {
+            options.global_context = config.context;
+        }
+
+        // Nothing to translate when targeting the template language
+        if target.is_lang(self.template_language) {
+            tracing::warn!(
+                lang = %target,
+                "target is the template language, skipping translation");
+            return Ok(TranslateResult {
+                translated: template.clone(),
+                template,
+                length: 0,
+                changed: false,
+                errors: Vec::new(),
+                overridden: Vec::new(),
+                deleted: Vec::new(),
+                characters: 0,
+                deferred: Vec::new(),
+                skipped: Vec::new(),
+                warnings: Vec::new(),
+                provenance: BTreeMap::new(),
+                diff: Default::default(),
+                cache_changed: false,
+            });
+        }
+
+        let mut output = if let Some(existing) = options.existing.take() {
+            existing
+        } else if let Some(code) = &options.target_code {
+            let file = self.load_path_or_default(self.file_path_code(code)?)?;
+            self.merge_parts(code, file)?
+        } else {
+            self.load_or_default(options.target_lang)?
+        };
+        let existing = output.clone();
+        let mut errors = Vec::new();
         let mut cached = Vec::new();
-        let mut translatable = Vec::new();
-        let diff = template.diff(&output, self.cache.get_file(&options.target_lang));
+        let mut translatable: IndexMap<Option<String>, Vec<String>> = IndexMap::new();
+        // Batches with Markdown or entities protected by XML tags
+        let mut tagged = HashSet::new();
+        let mut positions = HashMap::new();
+        let mut length = 0;
+        let budget = options
+            .character_budget
+            .or_else(|| lang.and_then(|lang| self.character_budget(&lang)));
+        let mut budgeted = HashSet::new();
+        let mut budget_used = 0;
+        let mut deferred = Vec::new();
+        let mut skipped = Vec::new();
+        let mut warnings = Vec::new();
+        let mut detected_sources = HashMap::new();
+        let mut excluded_changed = false;
+        let mut diff = {
+            let cache = self.cache();
+            let previous = if let Some(baseline) = &options.baseline {
+                Some(baseline)
+            } else {
+                lang.and_then(|lang| cache.get_file(&lang))
+            };
+            if options.ignore_whitespace {
+                template.diff_ignore_whitespace(&output, previous)
+            } else {
+                template.diff(&output, previous)
+            }
+        };
+
+        if options.source_hash {
+            // Detect changed source values from the stored hashes
+            for entry in template.entries() {
+                let key = entry.key().as_ref();
+                if let (Some(hash), Some(source)) =
+                    (output.source_hash(key), entry.value().as_str())
+                {
+                    if hash != hash_source(source) {
+                        diff.update.insert(key.to_owned());
+                    }
+                }
+            }
+            // Keep meta data holding the hash for existing keys
+            diff.delete.retain(|key| {
+                !ArbKey::new(key)
+                    .data_key()
+                    .map(|key| template.contains_key(key))
+                    .unwrap_or(false)
+            });
+        }
+
+        // Whitespace only changes keep the existing translation
+        // with the surrounding whitespace of the new source and
+        // the cache is updated so the change is not detected again
+        let mut whitespace_changed = false;
+        if let (true, Some(lang)) = (options.ignore_whitespace, lang) {
+            for entry in template.translatable_entries() {
+                let key = entry.key().as_ref();
+                if diff.update.contains(key) || template.is_locked(key) {
+                    continue;
+                }
+                let Some(source) = entry.value().as_str() else {
+                    continue;
+                };
+                let cached = self
+                    .cache()
+                    .get_file(&lang)
+                    .and_then(|file| file.lookup(key))
+                    .and_then(|cached| cached.value().as_str().map(|s| s.to_owned()));
+                if cached.is_none_or(|cached| cached == source) {
+                    continue;
+                }
+                let translation = output
+                    .lookup(key)
+                    .and_then(|value| value.value().as_str().map(|s| s.to_owned()));
+                if let Some(translation) = translation {
+                    let (leading, _, trailing) = surrounding_whitespace(source);
+                    let (_, text, _) = surrounding_whitespace(&translation);
+                    let padded = format!("{}{}{}", leading, text, trailing);
+                    output.insert_translation(entry.key(), padded);
+                }
+                if !options.dry_run && !options.disable_cache {
+                    self.cache().add_entry(lang, entry.clone());
+                    whitespace_changed = true;
+                }
+            }
+        }
 
-        let overrides = if let Some(overrides) = &options.overrides {
-            overrides.get(&options.target_lang)
+        let overrides = if let (Some(overrides), Some(lang)) = (&options.overrides, lang) {
+            overrides.get(&lang)
         } else {
             None
         };
@@ -383,12 +1584,31 @@ impl Intl {
                 _ => false,
             };
 
+            // Locked keys keep the existing translation even
+            // when invalidated
+            if template.is_locked(entry.key().as_ref()) && output.contains_key(entry.key().as_ref())
+            {
+                if invalidated || diff.update.contains(entry.key().as_ref()) {
+                    tracing::warn!(key = %entry.key(), "locked key is stale");
+                    warnings.push(Warning::LockedStale {
+                        key: entry.key().to_string(),
+                    });
+                }
+                if entry.is_translatable() {
+                    skipped.push(SkippedKey::new(entry.key(), SkipReason::Locked));
+                }
+                continue;
+            }
+
             // Ignore if removed or not in the set of added keys.
             if !invalidated
                 && (diff.delete.contains(entry.key().as_ref())
                     || (!diff.create.contains(entry.key().as_ref())
                         && !diff.update.contains(entry.key().as_ref())))
             {
+                if entry.is_translatable() {
+                    skipped.push(SkippedKey::new(entry.key(), SkipReason::NotInDiff));
+                }
                 continue;
             }
 
@@ -396,10 +1616,35 @@ impl Intl {
             // so no need to translate
             if let Some(overrides) = overrides {
                 if overrides.lookup(entry.key().as_ref()).is_some() {
+                    if entry.is_translatable() {
+                        skipped.push(SkippedKey::new(entry.key(), SkipReason::Overridden));
+                    }
                     continue;
                 }
             }
 
+            // Excluded keys copy the source text
+            if entry.is_translatable() && template.is_excluded(entry.key().as_ref()) {
+                tracing::info!(key = %entry.key(), "exclude");
+                let source = entry.value().as_str().unwrap_or_default().to_owned();
+                match template.index_of(entry.key().as_ref()) {
+                    Some(index)
+                        if options.output_order == OutputOrder::Template
+                            && diff.create.contains(entry.key().as_ref())
+                            && index < output.len() =>
+                    {
+                        output.shift_insert_translation(index, entry.key(), source)
+                    }
+                    _ => output.insert_translation(entry.key(), source),
+                }
+                if let (false, false, Some(lang)) = (options.dry_run, options.disable_cache, lang) {
+                    self.cache().add_entry(lang, entry.clone());
+                    excluded_changed = true;
+                }
+                skipped.push(SkippedKey::new(entry.key(), SkipReason::Excluded));
+                continue;
+            }
+
             if entry.is_translatable() {
                 let placeholders = template.placeholders(entry.key())?;
                 if let Some(placeholders) = &placeholders {
@@ -417,42 +1662,121 @@ impl Intl {
 
                 // Verify the source placeholders are declared correctly
                 let names = if let Some(placeholders) = &placeholders {
-                    placeholders.verify(text)?;
+                    if let Err(e) = placeholders.verify(text) {
+                        if options.collect_errors {
+                            tracing::warn!(key = %entry.key(), error = %e, "skip");
+                            skipped.push(SkippedKey::new(entry.key(), SkipReason::Invalid));
+                            errors.push((entry.key().to_string(), e));
+                            continue;
+                        }
+                        return Err(e);
+                    }
                     Some(placeholders.to_vec())
                 } else {
                     None
                 };
 
-                // Replace placeholders with XML tags
-                let text = if let Some(names) = &names {
-                    let mut text = text.to_string();
-                    for name in names {
-                        text = text.replacen(
-                            &format!("{{{}}}", name),
-                            &format!("<ph>{}</ph>", name),
-                            1,
-                        );
+                // Surrounding whitespace is restored after translation
+                let (leading, text, trailing) = surrounding_whitespace(text);
+                let padding = (leading.to_owned(), trailing.to_owned());
+
+                // Protect Markdown segments with XML tags
+                let (text, segments) = match options.markup {
+                    Markup::Markdown => {
+                        let (text, segments) = protect_markdown(text);
+                        (Cow::Owned(text), segments)
                     }
-                    Cow::Owned(text)
+                    Markup::Text => (Cow::Borrowed(text), Vec::new()),
+                };
+
+                // Protect HTML entities with XML tags
+                let (text, entities) = if text.contains('&') {
+                    let (text, entities) = protect_entities(&text);
+                    (Cow::Owned(text), entities)
                 } else {
-                    Cow::Borrowed(text)
+                    (text, Vec::new())
                 };
 
-                let key_index = if diff.create.contains(entry.key().as_ref()) {
-                    template.contents.get_index_of(entry.key().as_ref())
+                let protected = text.to_string();
+
+                // Replace placeholders with XML tags or sentinels
+                let text = if let Some(names) = &names {
+                    Cow::Owned(options.placeholder_strategy.protect(&text, names))
+                } else {
+                    text
+                };
+
+                // Defer keys once the character budget is exhausted
+                if let Some(budget) = budget {
+                    if !budgeted.contains(text.as_ref()) {
+                        let count = text.chars().count();
+                        if budget_used + count > budget {
+                            tracing::warn!(key = %entry.key(), "character budget exceeded, defer");
+                            deferred.push(entry.key().to_string());
+                            skipped.push(SkippedKey::new(entry.key(), SkipReason::TooLong));
+                            continue;
+                        }
+                        budget_used += count;
+                        budgeted.insert(text.to_string());
+                    }
+                }
+
+                let key_index = if options.output_order == OutputOrder::Template
+                    && diff.create.contains(entry.key().as_ref())
+                {
+                    template.index_of(entry.key().as_ref())
                 } else {
                     None
                 };
 
                 if !options.dry_run {
-                    translatable.push(text.as_ref().to_string());
-                    if !options.disable_cache {
-                        self.cache.add_entry(options.target_lang, entry.clone());
+                    // Context from the meta data takes precedence and
+                    // the description of a tracked key is used as context
+                    // before the context for the namespace of the key
+                    let key = entry.key().as_ref();
+                    let context = template
+                        .context(key)
+                        .or_else(|| {
+                            template
+                                .tracks_description(key)
+                                .then(|| template.description(key))
+                                .flatten()
+                        })
+                        .or_else(|| self.namespace_context(key))
+                        .map(|s| s.to_owned())
+                        .or_else(|| options.global_context.clone());
+                    tracing::debug!(
+                        key = %entry.key(),
+                        context = ?context,
+                        "context");
+
+                    // Identical source strings are only translated once
+                    let batch = translatable.entry(context);
+                    let batch_index = batch.index();
+                    if !segments.is_empty() || !entities.is_empty() {
+                        tagged.insert(batch_index);
                     }
+                    let texts: &mut Vec<String> = batch.or_default();
+                    let position =
+                        if let Some(position) = positions.get(&(batch_index, text.to_string())) {
+                            *position
+                        } else {
+                            let position = texts.len();
+                            positions.insert((batch_index, text.to_string()), position);
+                            texts.push(text.to_string());
+                            position
+                        };
+                    length += 1;
                     cached.push(CachedEntry::Translate {
                         entry,
                         names,
                         index: key_index,
+                        batch: batch_index,
+                        position,
+                        segments,
+                        entities,
+                        protected,
+                        padding,
                     });
                 } else {
                     cached.push(CachedEntry::Entry(entry));
@@ -462,56 +1786,268 @@ impl Intl {
             }
         }
 
+        let cache_changed = !translatable.is_empty()
+            || !diff.delete.is_empty()
+            || excluded_changed
+            || whitespace_changed;
+        let plan = diff.clone();
+
         // Clean up any existing entries scheduled to be deleted
+        let mut deleted = Vec::new();
         for key in diff.delete {
             tracing::info!(key = %key, "delete");
             output.remove(&key);
-            self.cache.remove_entry(&options.target_lang, &key);
+            if let Some(lang) = &lang {
+                self.cache().remove_entry(lang, &key);
+            }
+            deleted.push(key);
         }
-
-        let length = translatable.len();
+        deleted.sort();
+
+        let unique = translatable
+            .values()
+            .map(|texts| texts.len())
+            .sum::<usize>();
+        let characters = translatable
+            .values()
+            .flatten()
+            .map(|text| text.chars().count())
+            .sum::<usize>();
 
         tracing::info!(
-            lang = %options.target_lang,
+            lang = %target,
             length = %length,
+            unique = %unique,
+            deferred = %deferred.len(),
             "translate");
 
-        if !translatable.is_empty() {
-            let mut request = TranslateTextRequest::new(translatable, options.target_lang);
-            request.tag_handling = Some(TagHandling::Xml);
-            request.ignore_tags = Some(vec!["ph".to_string()]);
+        let mut hashes = Vec::new();
+        // Dry runs keep the source text for keys that would be
+        // translated so the proposed file can be reviewed
+        if !translatable.is_empty() || options.dry_run {
+            // One request for each distinct context
+            let mut results = Vec::new();
+            let mut requests = Vec::new();
+            for (batch, (context, texts)) in translatable.into_iter().enumerate() {
+                let expected = texts.len();
+                let mut request = TranslateTextRequest::new(texts, target.clone());
+                // Sentinels only need tag handling for other protected text
+                if options.placeholder_strategy == PlaceholderStrategy::XmlTags
+                    || tagged.contains(&batch)
+                {
+                    request.tag_handling = Some(TagHandling::Xml);
+                    request.ignore_tags = Some(vec![
+                        "ph".to_string(),
+                        markdown_tag().to_string(),
+                        entity_tag().to_string(),
+                    ]);
+                }
+                request.context = context;
+                if let Some(glossary_id) = &options.glossary_id {
+                    request.glossary_id = Some(glossary_id.to_owned());
+                    request.source_lang = Some(self.template_language.base());
+                }
+
+                request.formality = options.formality;
+
+                let result = match api.translate_text(&request).await {
+                    Err(deepl::Error::FormalityUnsupported(lang)) => {
+                        tracing::warn!(lang = %lang, "formality not supported, retry without formality");
+                        warnings.push(Warning::FormalityUnsupported {
+                            lang: lang.to_string(),
+                        });
+                        request.formality = None;
+                        api.translate_text(&request).await?
+                    }
+                    result => result?,
+                };
+
+                // Retry the missing tail of a partial response
+                let mut translations = result.translations;
+                while translations.len() < expected {
+                    let received = translations.len();
+                    tracing::warn!(
+                        expected = %expected,
+                        received = %received,
+                        "partial response, retry missing texts");
+                    warnings.push(Warning::PartialResponse { expected, received });
+                    let mut retry = request.clone();
+                    retry.text = request.text[received..].to_vec();
+                    let result = api.translate_text(&retry).await?;
+                    if result.translations.is_empty() {
+                        break;
+                    }
+                    translations.extend(result.translations);
+                }
 
-            let mut result = api.translate_text(&request).await?;
+                if translations.len() != expected {
+                    return Err(Error::TranslationLength(expected, translations.len()));
+                }
+                results.push(translations);
 
-            if result.translations.len() != length {
-                return Err(Error::TranslationLength(length, result.translations.len()));
+                // Keep the request options for tag mismatch retries
+                request.text.clear();
+                requests.push(request);
             }
 
             for entry in cached {
                 match entry {
-                    CachedEntry::Entry(entry) => {
-                        output.insert_entry(entry);
-                    }
+                    CachedEntry::Entry(entry) => output.insert_entry(entry),
                     CachedEntry::Translate {
                         entry,
                         names,
                         index,
+                        batch,
+                        position,
+                        segments,
+                        entities,
+                        protected,
+                        padding,
                     } => {
-                        let translated = result.translations.remove(0).text;
+                        let mut translated = results[batch][position].text.clone();
+
+                        let detected = results[batch][position].detected_source_language;
+                        if requests[batch].source_lang.is_none()
+                            && detected.base() != self.template_language.base()
+                        {
+                            tracing::warn!(
+                                key = %entry.key(),
+                                detected = %detected,
+                                "detected source language mismatch");
+                            warnings.push(Warning::SourceMismatch {
+                                key: entry.key().to_string(),
+                                detected,
+                            });
+                        }
 
-                        // Revert placeholder XML tags
-                        let translation = if let Some(names) = names {
-                            let mut translation = translated;
-                            for name in names.into_iter() {
-                                let needle = format!("<ph>{}</ph>", name);
-                                let original = format!("{{{}}}", name);
-                                translation = translation.replacen(&needle, &original, 1);
+                        let source = entry.value().as_str().unwrap_or_default();
+                        if options.keep_on_empty
+                            && translated.trim().is_empty()
+                            && !source.trim().is_empty()
+                        {
+                            tracing::warn!(key = %entry.key(), "empty translation, keep existing value");
+                            warnings.push(Warning::EmptyTranslation {
+                                key: entry.key().to_string(),
+                            });
+                            skipped.push(SkippedKey::new(entry.key(), SkipReason::Empty));
+                            continue;
+                        }
+
+                        if options.source_hash {
+                            if let Some(source) = entry.value().as_str() {
+                                hashes.push((entry.key().to_string(), hash_source(source)));
                             }
-                            translation
+                        }
+
+                        let declared = names.clone();
+
+                        // Retry with sentinels when the placeholders did not
+                        // survive translation, Markdown and entities stay
+                        // protected by XML tags
+                        let names = match &names {
+                            Some(names)
+                                if !options.placeholder_strategy.matches(&translated, names) =>
+                            {
+                                tracing::warn!(
+                                    key = %entry.key(),
+                                    "placeholder mismatch, retry with sentinels");
+                                warnings.push(Warning::PlaceholderFallback {
+                                    key: entry.key().to_string(),
+                                });
+                                match retry_with_sentinels(
+                                    api,
+                                    &requests[batch],
+                                    &protected,
+                                    names,
+                                    !segments.is_empty() || !entities.is_empty(),
+                                    options.retry_preserve_formatting,
+                                )
+                                .await
+                                {
+                                    Ok(retried) => translated = retried,
+                                    Err(e) if options.collect_errors => {
+                                        tracing::warn!(key = %entry.key(), error = %e, "skip");
+                                        skipped.push(SkippedKey::new(
+                                            entry.key(),
+                                            SkipReason::Invalid,
+                                        ));
+                                        errors.push((entry.key().to_string(), e));
+                                        continue;
+                                    }
+                                    Err(e) => return Err(e),
+                                }
+                                None
+                            }
+                            _ => names,
+                        };
+
+                        // Revert placeholder XML tags or sentinels
+                        let translation = if let Some(names) = names {
+                            options.placeholder_strategy.restore(translated, &names)
                         } else {
                             translated
                         };
 
+                        // Restore protected HTML entities
+                        let translation = if entities.is_empty() {
+                            translation
+                        } else {
+                            restore_entities(&translation, &entities)
+                        };
+
+                        // Restore protected Markdown segments
+                        let translation = if segments.is_empty() {
+                            translation
+                        } else {
+                            restore_markdown(&translation, &segments)
+                        };
+
+                        // Restore the whitespace around the source text
+                        let (leading, trailing) = padding;
+                        let translation = if leading.is_empty() && trailing.is_empty() {
+                            translation
+                        } else {
+                            format!("{}{}{}", leading, translation.trim(), trailing)
+                        };
+
+                        // Verify the placeholders in each plural and select
+                        // branch against the same branch of the source
+                        if let Some(declared) = declared {
+                            if let Err(e) =
+                                Placeholders::new(declared).verify_translation(source, &translation)
+                            {
+                                if options.collect_errors {
+                                    tracing::warn!(key = %entry.key(), error = %e, "skip");
+                                    skipped.push(SkippedKey::new(entry.key(), SkipReason::Invalid));
+                                    errors.push((entry.key().to_string(), e));
+                                    continue;
+                                }
+                                return Err(e);
+                            }
+                        }
+
+                        let translation = if let Some(post_process) = &options.post_process {
+                            post_process(&translation)
+                        } else {
+                            translation
+                        };
+
+                        detected_sources.insert(entry.key().to_string(), detected);
+                        // Only record the source once the key was translated
+                        // so skipped and empty translations are retried
+                        if let (false, Some(lang)) = (options.disable_cache, lang) {
+                            let mut cache = self.cache();
+                            cache.add_entry(lang, entry.clone());
+                            if template.tracks_description(entry.key().as_ref()) {
+                                cache.add_description(
+                                    lang,
+                                    entry.key(),
+                                    template.description(entry.key().as_ref()),
+                                );
+                            }
+                            cache.add_translation(lang, entry.key(), translation.clone());
+                        }
+
                         if let Some(index) = index {
                             if index < output.len() {
                                 output.shift_insert_translation(index, entry.key(), translation)
@@ -526,27 +2062,354 @@ impl Intl {
             }
         }
 
+        // Meta data entries may have replaced the hashes
+        for (key, hash) in hashes {
+            output.set_source_hash(&key, hash);
+        }
+
+        let mut overridden = Vec::new();
         if let Some(overrides) = overrides {
             for entry in overrides.entries() {
                 tracing::info!(key = %entry.key().as_ref(), "override");
+                overridden.push(entry.key().to_string());
+                if let (false, Some(lang)) = (options.disable_cache, lang) {
+                    self.cache().remove_translation(&lang, entry.key().as_ref());
+                }
                 output.insert_entry(entry);
             }
         }
 
+        // Filter meta data from the template and meta data
+        // already in the existing translation
+        if let Some(filter) = &options.metadata_filter {
+            for (key, value) in output.contents.iter_mut() {
+                if ArbFile::is_metadata_key(key) {
+                    *value = filter.apply(value);
+                }
+            }
+        }
+
+        // Keep meta data next to the key it describes
+        if options.output_order == OutputOrder::Sorted {
+            output.sort_keys();
+        } else {
+            output.group_metadata();
+        }
+
         // Update the cache file
-        if !options.disable_cache {
+        let cache_changed = cache_changed && !options.disable_cache && lang.is_some();
+        if cache_changed && !self.defer_cache {
             self.write_cache()?;
         }
 
+        let changed = !output.contents.iter().eq(existing.contents.iter());
+
+        let mut provenance = BTreeMap::new();
+        for entry in template.translatable_entries() {
+            let key = entry.key().as_ref();
+            let Some(text) = output
+                .lookup(key)
+                .and_then(|value| value.value().as_str().map(|s| s.to_owned()))
+            else {
+                continue;
+            };
+            let detected_source = detected_sources.get(key).copied();
+            let origin = if overridden.iter().any(|k| k == key) {
+                Provenance::Override
+            } else if template.is_excluded(key) {
+                Provenance::Excluded
+            } else if detected_source.is_some() {
+                Provenance::Machine
+            } else {
+                Provenance::Cached
+            };
+            provenance.insert(
+                key.to_owned(),
+                KeyProvenance {
+                    provenance: origin,
+                    characters: text.chars().count(),
+                    detected_source: detected_source.filter(|_| origin == Provenance::Machine),
+                },
+            );
+        }
+
         Ok(TranslateResult {
             template,
             translated: output,
             length,
+            changed,
+            errors,
+            overridden,
+            deleted,
+            characters,
+            deferred,
+            skipped,
+            warnings,
+            provenance,
+            diff: plan,
+            cache_changed,
         })
     }
 
+    /// Translate the descriptions in the template into translator
+    /// notes in the meta data of a translation.
+    ///
+    /// Translated strings are not modified; only keys that exist
+    /// in the translation are annotated. When this is a dry run
+    /// no requests are made and the translation is returned as-is.
+    pub async fn translate_descriptions(
+        &self,
+        api: &impl TranslationProvider,
+        lang: Lang,
+        dry_run: bool,
+    ) -> Result<ArbFile> {
+        let template = self.template_content()?;
+        let mut output = self.load_or_default(lang)?;
+
+        let mut keys = Vec::new();
+        let mut texts = Vec::new();
+        for entry in template.translatable_entries() {
+            let key = entry.key().as_ref();
+            if let (true, Some(description)) = (output.contains_key(key), template.description(key))
+            {
+                tracing::info!(key = %key, "prepare description");
+                keys.push(key.to_owned());
+                texts.push(description.to_owned());
+            }
+        }
+
+        if dry_run || texts.is_empty() {
+            return Ok(output);
+        }
+
+        let expected = texts.len();
+        let mut request = TranslateTextRequest::new(texts, lang);
+        request.source_lang = Some(self.template_language.base());
+        let result = api.translate_text(&request).await?;
+        if result.translations.len() != expected {
+            return Err(Error::TranslationLength(
+                expected,
+                result.translations.len(),
+            ));
+        }
+
+        let code = lang.to_string();
+        for (key, translation) in keys.iter().zip(result.translations) {
+            output.set_translator_note(key, &code, translation.text);
+        }
+        Ok(output)
+    }
+
+    /// Translate a single key from the template regardless
+    /// of the diff.
+    ///
+    /// The key is translated using [Intl::translate] so the
+    /// glossary, formality, context, overrides, locked keys and
+    /// markup are handled in the same way; the translation files
+    /// and the cache are not updated.
+    ///
+    /// Returns [Error::KeySkipped] when the key exceeds the
+    /// character budget or the translation is empty.
+    pub async fn translate_key(
+        &self,
+        api: &impl TranslationProvider,
+        mut options: TranslationOptions,
+        key: &str,
+    ) -> Result<String> {
+        let lang = options.target_lang;
+        let template = self.template_content()?;
+        if !template
+            .lookup(key)
+            .map(|entry| entry.is_translatable())
+            .unwrap_or(false)
+        {
+            return Err(Error::UnknownKey(key.to_owned()));
+        }
+        tracing::info!(key = %key, lang = %lang, "translate key");
+
+        // Other keys match the template so only the key is translated
+        let current = self.load_or_default(lang)?;
+        let mut existing = template.clone();
+        match current
+            .lookup(key)
+            .and_then(|entry| entry.value().as_str().map(|s| s.to_owned()))
+        {
+            Some(value) => existing.insert_translation(&ArbKey::new(key), value),
+            None => {
+                existing.remove(key);
+            }
+        }
+
+        options.invalidation = Some(Invalidation::Keys(vec![key.to_owned()]));
+        options.baseline = Some(template.clone());
+        options.existing = Some(existing);
+        options.target_code = None;
+        options.dry_run = false;
+        options.ignore_whitespace = false;
+        options.disable_cache = true;
+
+        let mut result = self.translate(api, options).await?;
+        if let Some(index) = result.errors.iter().position(|(name, _)| name == key) {
+            return Err(result.errors.remove(index).1);
+        }
+        let reason = result
+            .skipped
+            .iter()
+            .find(|skipped| skipped.key == key)
+            .map(|skipped| skipped.reason);
+        let value = result
+            .translated
+            .lookup(key)
+            .and_then(|entry| entry.value().as_str().map(|s| s.to_owned()));
+        // Locked and overridden keys return the existing value
+        match (reason, value) {
+            (Some(reason @ (SkipReason::TooLong | SkipReason::Empty)), _) => {
+                Err(Error::KeySkipped(key.to_owned(), reason))
+            }
+            (_, Some(value)) => Ok(value),
+            (reason, None) => Err(Error::KeySkipped(
+                key.to_owned(),
+                reason.unwrap_or(SkipReason::Empty),
+            )),
+        }
+    }
+
+    /// Propose template entries for keys that only exist
+    /// in a translation.
+    ///
+    /// Keys present in the translation but missing from the
+    /// template are translated from the language back into the
+    /// template language; meta data for the keys is copied from
+    /// the translation. When this is a dry run no requests are
+    /// made and the proposed entries keep the translated text.
+    pub async fn backfill(
+        &self,
+        api: &impl TranslationProvider,
+        lang: Lang,
+        dry_run: bool,
+    ) -> Result<ArbFile> {
+        let template = self.template_content()?;
+        let translation = self.load(lang)?;
+
+        let mut output = ArbFile::default();
+        let mut keys = Vec::new();
+        let mut texts = Vec::new();
+        for entry in translation.translatable_entries() {
+            let key = entry.key().as_ref();
+            if template.contains_key(key) {
+                continue;
+            }
+            tracing::info!(key = %key, "backfill");
+            let meta_key = format!("@{}", key);
+            keys.push(key.to_owned());
+            texts.push(entry.value().as_str().unwrap_or_default().to_owned());
+            output.insert_entry(entry);
+            if let Some(meta) = translation.lookup(&meta_key) {
+                output.insert_entry(meta);
+            }
+        }
+
+        if dry_run || texts.is_empty() {
+            return Ok(output);
+        }
+
+        let translations =
+            translate_strings(api, &texts, self.template_language.into(), Some(lang)).await?;
+        for (key, text) in keys.iter().zip(translations) {
+            output.insert_translation(&ArbKey::new(key), text);
+        }
+        Ok(output)
+    }
+
+    /// Create a glossary from human-provided overrides.
+    ///
+    /// Each override is paired with the source text in the
+    /// template to create the glossary entries.
+    pub async fn create_glossary(
+        &self,
+        api: &DeeplApi,
+        name: impl AsRef<str>,
+        target_lang: Lang,
+        overrides: &ArbFile,
+    ) -> Result<Glossary> {
+        let template = self.template_content()?;
+        let entries = template.glossary_entries(overrides);
+        tracing::info!(
+            lang = %target_lang,
+            length = %entries.len(),
+            "glossary");
+        let request =
+            CreateGlossaryRequest::new(name, self.template_language, target_lang, &entries);
+        Ok(api.create_glossary(&request).await?)
+    }
+
+    /// Compare a translation file against the values recorded
+    /// in the cache when keys were last translated.
+    ///
+    /// Keys whose current value differs from the recorded value
+    /// have been edited (or removed) since they were translated.
+    pub fn drift(&self, lang: Lang) -> Result<Vec<DriftEntry>> {
+        let current = self.load_or_default(lang)?;
+        let cache = self.cache();
+        let Some(translations) = cache.get_translations(&lang) else {
+            return Ok(Vec::new());
+        };
+        let mut drift = Vec::new();
+        for entry in translations.entries() {
+            let key = entry.key().as_ref();
+            let Some(cached) = entry.value().as_str() else {
+                continue;
+            };
+            let value = current
+                .lookup(key)
+                .and_then(|entry| entry.value().as_str().map(|s| s.to_owned()));
+            if value.as_deref() != Some(cached) {
+                drift.push(DriftEntry {
+                    key: key.to_owned(),
+                    cached: cached.to_owned(),
+                    current: value,
+                });
+            }
+        }
+        Ok(drift)
+    }
+
+    /// Translated values that were edited by hand since they
+    /// were last translated.
+    ///
+    /// Keys removed from the translation are not included.
+    pub fn manual_edits(&self, lang: Lang) -> Result<ArbFile> {
+        let mut output = ArbFile::default();
+        for entry in self.drift(lang)? {
+            if let Some(current) = entry.current {
+                output.insert_translation(&ArbKey::new(&entry.key), current);
+            }
+        }
+        Ok(output)
+    }
+
+    /// Remove cache entries for languages that no longer
+    /// have a translation file.
+    ///
+    /// Returns the languages that were removed; the cache file
+    /// is only written when languages were removed and this is
+    /// not a dry run.
+    pub fn prune_cache(&self, dry_run: bool) -> Result<Vec<Lang>> {
+        let languages = self.list_translated()?.into_keys().collect::<Vec<_>>();
+        let removed = self.cache().retain_languages(&languages);
+        if !removed.is_empty() && !dry_run {
+            self.write_cache()?;
+        }
+        Ok(removed)
+    }
+
+    /// Path to the cache file.
+    pub fn cache_path(&self) -> Result<PathBuf> {
+        Ok(self.arb_directory()?.join(CACHE_FILE))
+    }
+
     fn read_cache(&self) -> Result<ArbCache> {
-        let cache_path = self.arb_directory()?.join(CACHE_FILE);
+        let cache_path = self.cache_path()?;
         if cache_path.try_exists()? {
             let mut cache_file = std::fs::File::open(cache_path)?;
             Ok(serde_json::from_reader(&mut cache_file)?)
@@ -556,9 +2419,204 @@ impl Intl {
     }
 
     fn write_cache(&self) -> Result<()> {
-        let cache_path = self.arb_directory()?.join(CACHE_FILE);
-        let mut cache_file = std::fs::File::create(cache_path)?;
-        serde_json::to_writer_pretty(&mut cache_file, &self.cache)?;
+        let cache_path = self.output_directory()?.join(CACHE_FILE);
+        // Hold the lock while writing so concurrent translations
+        // always write a consistent snapshot of the cache
+        let cache = self.cache();
+        let mut content = serde_json::to_string_pretty(&*cache)?;
+        content.push('\n');
+        std::fs::write(cache_path, content)?;
         Ok(())
     }
 }
+
+/// Translate strings outside of an application resource bundle.
+///
+/// Placeholders are detected by scanning each string for `{name}`
+/// and are protected along with HTML entities; empty strings are
+/// returned without calling the API.
+pub async fn translate_strings(
+    api: &impl TranslationProvider,
+    texts: &[String],
+    target: TargetLang,
+    source_lang: Option<Lang>,
+) -> Result<Vec<String>> {
+    let mut prepared = Vec::new();
+    let mut positions = Vec::new();
+    for text in texts {
+        if text.trim().is_empty() {
+            positions.push(None);
+            continue;
+        }
+        let names = placeholder_names(text);
+        let (mut protected, entities) = protect_entities(text);
+        for name in &names {
+            protected =
+                protected.replacen(&format!("{{{}}}", name), &format!("<ph>{}</ph>", name), 1);
+        }
+        positions.push(Some(prepared.len()));
+        prepared.push((protected, names, entities));
+    }
+
+    if prepared.is_empty() {
+        return Ok(texts.to_vec());
+    }
+
+    let expected = prepared.len();
+    let mut request = TranslateTextRequest::new(
+        prepared
+            .iter()
+            .map(|(text, _, _)| text.to_owned())
+            .collect(),
+        target,
+    );
+    request.tag_handling = Some(TagHandling::Xml);
+    request.ignore_tags = Some(vec!["ph".to_string(), entity_tag().to_string()]);
+    request.source_lang = source_lang.map(|lang| lang.base());
+    let result = api.translate_text(&request).await?;
+    if result.translations.len() != expected {
+        return Err(Error::TranslationLength(
+            expected,
+            result.translations.len(),
+        ));
+    }
+
+    let mut output = Vec::new();
+    for (text, position) in texts.iter().zip(positions) {
+        if let Some(position) = position {
+            let (_, names, entities) = &prepared[position];
+            let mut translation = result.translations[position].text.clone();
+            for name in names {
+                translation = translation.replacen(
+                    &format!("<ph>{}</ph>", name),
+                    &format!("{{{}}}", name),
+                    1,
+                );
+            }
+            output.push(restore_entities(&translation, entities));
+        } else {
+            output.push(text.to_owned());
+        }
+    }
+    Ok(output)
+}
+
+/// Parse an application resource bundle ignoring a leading
+/// UTF-8 byte order mark.
+///
+/// Errors include the path of the file so a malformed
+/// translation can be located.
+fn parse_arb(path: &Path, content: &str) -> Result<ArbFile> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    serde_json::from_str(content).map_err(|e| {
+        let message = if content.trim_start().starts_with('{') {
+            e.to_string()
+        } else {
+            "expected a JSON object of key/value pairs".to_owned()
+        };
+        Error::InvalidArb(path.to_path_buf(), message)
+    })
+}
+
+/// Split text into leading whitespace, the trimmed text and
+/// trailing whitespace.
+fn surrounding_whitespace(text: &str) -> (&str, &str, &str) {
+    let rest = text.trim_start();
+    let leading = &text[..text.len() - rest.len()];
+    let trimmed = rest.trim_end();
+    (leading, trimmed, &rest[trimmed.len()..])
+}
+
+/// Determine if every placeholder tag survived translation
+/// exactly once and no stray tags were introduced.
+fn placeholder_tags_match(translated: &str, names: &[&str]) -> bool {
+    translated.matches("<ph>").count() == names.len()
+        && translated.matches("</ph>").count() == names.len()
+        && names
+            .iter()
+            .all(|name| translated.contains(&format!("<ph>{}</ph>", name)))
+}
+
+/// Translate a single source string with placeholders
+/// protected by sentinels.
+///
+/// Placeholders are replaced with neutral sentinels that are
+/// restored after translation; other request options are copied
+/// from the original request. Markdown and entity tags are kept
+/// so they are restored in the same way as the first attempt.
+///
+/// When a placeholder is lost and `preserve_formatting` is set the
+/// request is sent once more with formatting preserved and sentence
+/// splitting disabled.
+async fn retry_with_sentinels(
+    api: &impl TranslationProvider,
+    options: &TranslateTextRequest,
+    source: &str,
+    names: &[&str],
+    tagged: bool,
+    preserve_formatting: bool,
+) -> Result<String> {
+    match translate_with_sentinels(api, options, source, names, tagged).await {
+        Err(Error::PlaceholderLost(name, _)) if preserve_formatting => {
+            tracing::warn!(
+                placeholder = %name,
+                "placeholder lost, retry preserving formatting");
+            let mut request = options.clone();
+            request.preserve_formatting = Some(true);
+            request.split_sentences = Some(SplitSentences::None);
+            translate_with_sentinels(api, &request, source, names, tagged).await
+        }
+        result => result,
+    }
+}
+
+/// Translate a single source string replacing placeholders
+/// with sentinels.
+///
+/// When the source contains Markdown or entity tags they are
+/// still ignored using the XML tag handling of the API.
+async fn translate_with_sentinels(
+    api: &impl TranslationProvider,
+    options: &TranslateTextRequest,
+    source: &str,
+    names: &[&str],
+    tagged: bool,
+) -> Result<String> {
+    let strategy = PlaceholderStrategy::Sentinel;
+    let mut request = options.clone();
+    request.text = vec![strategy.protect(source, names)];
+    if tagged {
+        request.tag_handling = Some(TagHandling::Xml);
+        request.ignore_tags = Some(vec![markdown_tag().to_string(), entity_tag().to_string()]);
+    } else {
+        request.tag_handling = None;
+        request.ignore_tags = None;
+    }
+    let result = api.translate_text(&request).await?;
+    let Some(translation) = result.translations.into_iter().next() else {
+        return Err(Error::TranslationLength(1, 0));
+    };
+
+    for (index, name) in names.iter().enumerate() {
+        if !translation.text.contains(&strategy.marker(index, name)) {
+            return Err(Error::PlaceholderLost(name.to_string(), source.to_owned()));
+        }
+    }
+    Ok(strategy.restore(translation.text, names))
+}
+
+/// Match a file name against a pattern where `*` matches
+/// any sequence of characters.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|index| name.is_char_boundary(*index))
+                .any(|index| wildcard_match(rest, &name[index..]))
+        }
+    }
+}
@@ -4,26 +4,39 @@
 //!
 //! Create a translation application resource bundle:
 //!
-//! ```
-//! use arb_lib::{Intl, deepl::{DeeplApi, ApiOptions, Lang}};
+//! ```no_run
+//! use arb_lib::{Intl, TranslationOptions, deepl::{DeeplApi, ApiOptions, Lang}};
 //!
-//! let api_key: std::env::var("DEEPL_API_KEY").unwrap();
+//! # async fn run() -> arb_lib::Result<()> {
+//! let api_key = std::env::var("DEEPL_API_KEY").unwrap();
 //! let api = DeeplApi::new(ApiOptions::new(api_key));
 //! let options = TranslationOptions::new(Lang::Fr);
-//! let mut intl = Intl::new("l10n.yaml")?;
+//! let intl = Intl::new("l10n.yaml")?;
 //! let result = intl.translate(&api, options).await?;
 //! println!("{:#?}", result);
+//! # Ok(())
+//! # }
 //! ```
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 mod arb;
+mod entity;
 mod error;
+mod icu;
 mod intl;
+mod markdown;
+mod patch;
+mod provider;
 
 pub use arb::*;
+pub use entity::*;
 pub use error::Error;
+pub use icu::*;
 pub use intl::*;
+pub use markdown::*;
+pub use patch::*;
+pub use provider::*;
 
 /// Result type for the library.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,95 @@
+use crate::entity::restore_tagged;
+use std::{fmt, str::FromStr};
+
+const TAG: &str = "md";
+
+/// Variants for handling markup in source strings.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Markup {
+    /// Plain text, only placeholders are protected.
+    #[default]
+    Text,
+    /// Markdown text.
+    ///
+    /// Code fences, inline code and link targets are
+    /// protected from translation.
+    Markdown,
+}
+
+impl AsRef<str> for Markup {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Text => "text",
+            Self::Markdown => "markdown",
+        }
+    }
+}
+
+impl fmt::Display for Markup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for Markup {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "text" => Self::Text,
+            "markdown" => Self::Markdown,
+            _ => return Err(crate::Error::InvalidMarkup(s.to_string())),
+        })
+    }
+}
+
+/// Name of the XML tag used to protect Markdown segments.
+pub fn markdown_tag() -> &'static str {
+    TAG
+}
+
+/// Protect Markdown segments from translation.
+///
+/// Code fences, inline code and link targets are replaced with
+/// numbered XML tags; the returned segments are used to restore
+/// the original text after translation.
+pub fn protect_markdown(text: &str) -> (String, Vec<String>) {
+    let mut output = String::new();
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (skip, length) = if rest.starts_with("```") {
+            (0, closing(rest, 3, "```").map(|end| end + 3))
+        } else if rest.starts_with('`') {
+            (0, closing(rest, 1, "`").map(|end| end + 1))
+        } else if rest.starts_with("](") {
+            (1, closing(rest, 2, ")").map(|end| end + 1))
+        } else {
+            (0, None)
+        };
+
+        if let Some(length) = length {
+            output.push_str(&rest[..skip]);
+            output.push_str(&format!("<{}>{}</{}>", TAG, segments.len(), TAG));
+            segments.push(rest[skip..length].to_string());
+            rest = &rest[length..];
+        } else {
+            let mut chars = rest.chars();
+            if let Some(c) = chars.next() {
+                output.push(c);
+            }
+            rest = chars.as_str();
+        }
+    }
+    (output, segments)
+}
+
+/// Restore Markdown segments after translation.
+pub fn restore_markdown(text: &str, segments: &[String]) -> String {
+    restore_tagged(text, TAG, segments)
+}
+
+/// Find the end of a delimited segment after an offset.
+fn closing(text: &str, offset: usize, delimiter: &str) -> Option<usize> {
+    text[offset..].find(delimiter).map(|end| end + offset)
+}
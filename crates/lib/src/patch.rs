@@ -0,0 +1,151 @@
+/// Lines of context around each change.
+const CONTEXT: usize = 3;
+
+/// Line operation in a diff.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Create a unified diff between two texts.
+///
+/// Paths are written in the git style as `a/` and `b/` file
+/// headers; when the old text is empty the old path is
+/// `/dev/null`. Returns an empty string when the texts are equal.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let ops = diff_lines(&old_lines, &new_lines);
+    if ops.iter().all(|(op, _)| *op == Op::Equal) {
+        return String::new();
+    }
+
+    let mut output = format!("diff --git a/{} b/{}\n", path, path);
+    if old_lines.is_empty() {
+        output.push_str("--- /dev/null\n");
+    } else {
+        output.push_str(&format!("--- a/{}\n", path));
+    }
+    output.push_str(&format!("+++ b/{}\n", path));
+
+    for (start, end) in hunks(&ops) {
+        // Lines consumed before the hunk
+        let old_before = ops[..start]
+            .iter()
+            .filter(|(op, _)| *op != Op::Insert)
+            .count();
+        let new_before = ops[..start]
+            .iter()
+            .filter(|(op, _)| *op != Op::Delete)
+            .count();
+        let hunk = &ops[start..end];
+        let old_count = hunk.iter().filter(|(op, _)| *op != Op::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _)| *op != Op::Delete).count();
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_before, old_count),
+            range(new_before, new_count)
+        ));
+        for (op, line) in hunk {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            output.push(prefix);
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Format a hunk range, empty ranges refer to the line
+/// before the change.
+fn range(before: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", before)
+    } else {
+        format!("{},{}", before + 1, count)
+    }
+}
+
+/// Group changes into hunks with surrounding context.
+///
+/// Returns the start and end index of each hunk in the operations.
+fn hunks(ops: &[(Op, &str)]) -> Vec<(usize, usize)> {
+    let mut output: Vec<(usize, usize)> = Vec::new();
+    for (index, (op, _)) in ops.iter().enumerate() {
+        if *op == Op::Equal {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(ops.len());
+        match output.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => output.push((start, end)),
+        }
+    }
+    output
+}
+
+/// Compute the line operations to transform the old lines
+/// into the new lines using the longest common subsequence.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    // Common prefix and suffix do not need to be compared
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let rows = old_middle.len();
+    let columns = new_middle.len();
+    let mut lengths = vec![0usize; (rows + 1) * (columns + 1)];
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            lengths[i * (columns + 1) + j] = if old_middle[i] == new_middle[j] {
+                lengths[(i + 1) * (columns + 1) + j + 1] + 1
+            } else {
+                lengths[(i + 1) * (columns + 1) + j].max(lengths[i * (columns + 1) + j + 1])
+            };
+        }
+    }
+
+    let mut ops = old[..prefix]
+        .iter()
+        .map(|line| (Op::Equal, *line))
+        .collect::<Vec<_>>();
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < columns {
+        if old_middle[i] == new_middle[j] {
+            ops.push((Op::Equal, old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * (columns + 1) + j] >= lengths[i * (columns + 1) + j + 1] {
+            ops.push((Op::Delete, old_middle[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, new_middle[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_middle[i..].iter().map(|line| (Op::Delete, *line)));
+    ops.extend(new_middle[j..].iter().map(|line| (Op::Insert, *line)));
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (Op::Equal, *line)),
+    );
+    ops
+}
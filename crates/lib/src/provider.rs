@@ -0,0 +1,69 @@
+use deepl::{DeeplApi, TranslateTextRequest, TranslateTextResponse};
+use std::future::Future;
+
+/// Service that translates text.
+///
+/// Implemented by [DeeplApi]; wrap providers in a
+/// [FallbackProvider] to switch to a secondary provider
+/// when the primary is unavailable.
+pub trait TranslationProvider: Sync {
+    /// Translate the texts in a request.
+    fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> impl Future<Output = deepl::Result<TranslateTextResponse>> + Send;
+}
+
+impl TranslationProvider for DeeplApi {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        DeeplApi::translate_text(self, request).await
+    }
+}
+
+/// Provider that uses a secondary provider when the primary
+/// fails because the quota is exceeded or the service is
+/// unavailable.
+pub struct FallbackProvider<P, S> {
+    primary: P,
+    secondary: S,
+}
+
+impl<P, S> FallbackProvider<P, S> {
+    /// Create a fallback provider.
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Determine if an error from the primary provider
+    /// should be retried with the secondary provider.
+    pub fn is_fallback_error(error: &deepl::Error) -> bool {
+        match error {
+            // 456 is returned when the quota is exceeded
+            deepl::Error::Api(status, _) => *status == 456 || *status >= 500,
+            deepl::Error::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+impl<P, S> TranslationProvider for FallbackProvider<P, S>
+where
+    P: TranslationProvider,
+    S: TranslationProvider,
+{
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        match self.primary.translate_text(request).await {
+            Err(e) if Self::is_fallback_error(&e) => {
+                tracing::warn!(error = %e, "primary provider failed, use fallback provider");
+                self.secondary.translate_text(request).await
+            }
+            result => result,
+        }
+    }
+}
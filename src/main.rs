@@ -1,9 +1,10 @@
 use anyhow::{anyhow, Result};
 use arb_lib::{
-    deepl::{ApiOptions, DeeplApi, Lang, LanguageType},
-    ArbFile, ArbKey, Intl, Invalidation, TranslationOptions,
+    deepl::{parse_config_api_key, ApiOptions, DeeplApi, Lang, LanguageType, TargetLang},
+    translate_strings, unified_diff, ArbFile, ArbKey, Intl, Invalidation, LocaleFormat, Markup,
+    MetadataFilter, OutputOrder, PlaceholderStrategy, SkippedKey, TranslationOptions, Warning,
 };
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
@@ -12,13 +13,78 @@ use std::{
 
 use csv::{ReaderBuilder, Writer, WriterBuilder};
 
-#[derive(Debug, Serialize, Deserialize)]
-struct CsvRow {
+/// Row in a comparison with a target and correction
+/// column for each language.
+#[derive(Debug)]
+struct CompareRow {
     id: String,
     source: String,
-    target: String,
-    correction: String,
-    comment: String,
+    columns: Vec<(String, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CharacterCount {
+    id: String,
+    characters: usize,
+}
+
+/// Summary of a translation for a language.
+#[derive(Debug, Serialize)]
+struct TranslateSummary {
+    lang: String,
+    translated: usize,
+    skipped: Vec<String>,
+    overridden: Vec<String>,
+    deleted: Vec<String>,
+    characters: usize,
+    deferred: Vec<String>,
+    skipped_keys: Vec<SkippedKey>,
+    warnings: Vec<Warning>,
+    changed: bool,
+    #[serde(skip)]
+    plan: LanguagePlan,
+    #[serde(skip)]
+    cache_changed: bool,
+}
+
+/// Planned changes for a language.
+#[derive(Debug, Serialize)]
+struct LanguagePlan {
+    create: Vec<String>,
+    update: Vec<String>,
+    delete: Vec<String>,
+    total: usize,
+}
+
+/// Configured languages grouped by support from the API.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct LanguageSupport {
+    supported: Vec<Lang>,
+    unsupported: Vec<Lang>,
+    source_only: Vec<Lang>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResolvedConfig {
+    file: PathBuf,
+    arb_dir: String,
+    arb_directory: PathBuf,
+    template_arb_file: String,
+    template_language: Lang,
+    name_prefix: String,
+    overrides_dir: Option<PathBuf>,
+    cache_path: PathBuf,
+}
+
+/// Output format for reports.
+#[derive(Debug, Default, Copy, Clone, ValueEnum)]
+pub enum ReportFormat {
+    /// JSON document.
+    #[default]
+    Json,
+    /// CSV document.
+    Csv,
 }
 
 #[derive(Parser, Debug)]
@@ -28,86 +94,366 @@ pub struct Arb {
     cmd: Command,
 }
 
+/// Connection settings for the API.
+///
+/// Defaults are read from the environment; explicit
+/// flags take precedence.
+#[derive(Debug, Clone, Args)]
+pub struct ConnectionArgs {
+    /// API endpoint URL.
+    #[clap(long, env = "DEEPL_ENDPOINT")]
+    endpoint: Option<String>,
+
+    /// Request timeout in seconds.
+    #[clap(long, env = "DEEPL_TIMEOUT")]
+    timeout: Option<u64>,
+
+    /// Maximum retries for failed requests.
+    #[clap(long, env = "DEEPL_MAX_RETRIES")]
+    max_retries: Option<u32>,
+
+    /// API path prefix.
+    #[clap(long, env = "DEEPL_API_VERSION")]
+    api_version: Option<String>,
+}
+
+/// Arguments shared by the translate and update commands.
+#[derive(Debug, Clone, Args)]
+pub struct TranslateArgs {
+    /// API key.
+    #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+    api_key: Option<String>,
+
+    #[clap(flatten)]
+    connection: ConnectionArgs,
+
+    /// Invalidate all keys.
+    #[clap(short, long)]
+    force: bool,
+
+    /// Invalidate specific keys.
+    #[clap(short, long)]
+    invalidate: Vec<String>,
+
+    /// Directory of human-translated overrides.
+    #[clap(long)]
+    overrides: Option<PathBuf>,
+
+    /// Glossary identifier.
+    #[clap(long)]
+    glossary: Option<String>,
+
+    /// Context applied to every translation.
+    #[clap(long)]
+    context: Option<String>,
+
+    /// Markup handling for source strings (text or markdown).
+    #[clap(long, default_value = "text")]
+    markup: Markup,
+
+    /// Template file used to detect updates instead of the cache.
+    #[clap(long)]
+    baseline: Option<PathBuf>,
+
+    /// Order of the keys in translations (template, sorted or preserve).
+    #[clap(long, default_value = "template")]
+    output_order: OutputOrder,
+
+    /// Do not update keys when the source text only changed
+    /// by whitespace.
+    #[clap(long)]
+    ignore_whitespace: bool,
+
+    /// Protect placeholders with XML tags or sentinels (xml-tags or sentinel).
+    #[clap(long, default_value = "xml-tags")]
+    placeholder_strategy: PlaceholderStrategy,
+
+    /// Do not retry with formatting preserved when placeholders
+    /// are lost in a translation.
+    #[clap(long)]
+    no_retry_preserve_formatting: bool,
+
+    /// Skip keys with invalid placeholders and report them.
+    #[clap(long)]
+    collect_errors: bool,
+
+    /// Translate and write to disc.
+    #[clap(long)]
+    apply: bool,
+
+    /// Directory to write translations and the cache.
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
+
+    /// Maximum API requests per second.
+    #[clap(long)]
+    rate_limit: Option<f64>,
+
+    /// Only copy these meta data fields into translations.
+    #[clap(long, conflicts_with = "metadata_deny")]
+    metadata_allow: Vec<String>,
+
+    /// Do not copy these meta data fields into translations.
+    #[clap(long)]
+    metadata_deny: Vec<String>,
+
+    /// Store source hashes in the translated meta data.
+    #[clap(long)]
+    source_hash: bool,
+
+    /// Maximum characters to send for each language.
+    #[clap(long)]
+    character_budget: Option<usize>,
+
+    /// Write the plan for each language to a JSON file.
+    #[clap(long)]
+    report: Option<PathBuf>,
+
+    /// Print a JSON summary for each language.
+    #[clap(long)]
+    json: bool,
+
+    /// Print a unified diff of the changes to each translation.
+    #[clap(long, conflicts_with = "json")]
+    patch: bool,
+
+    /// Error on ARB files that do not parse to a language.
+    #[clap(long)]
+    strict: bool,
+
+    /// Format of languages in file names (underscore, hyphen,
+    /// underscore-region or hyphen-region).
+    #[clap(long)]
+    locale_format: Option<LocaleFormat>,
+
+    /// Check target languages are supported by the API before translating.
+    #[clap(long)]
+    validate_lang: bool,
+
+    /// Write the provenance of each key to a `.metadata.json`
+    /// file next to each translation.
+    #[clap(long)]
+    metadata_sidecar: bool,
+
+    /// File name prefix.
+    #[clap(short, long)]
+    name_prefix: Option<String>,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Command {
     /// Translate the template to a language.
     #[clap(alias = "tl")]
     Translate {
+        #[clap(flatten)]
+        args: TranslateArgs,
+
+        /// Target languages.
+        #[clap(short, long, required_unless_present = "code")]
+        lang: Vec<Lang>,
+
+        /// Raw target language code for languages not yet supported.
+        #[clap(long, conflicts_with = "lang")]
+        code: Option<String>,
+
+        /// Write the translation to this file instead of the
+        /// file for the language.
+        #[clap(short, long, conflicts_with = "out_dir")]
+        output: Option<PathBuf>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Update existing translations.
+    #[clap(alias = "up")]
+    Update {
+        #[clap(flatten)]
+        args: TranslateArgs,
+
+        /// Only write files when all languages succeed.
+        #[clap(long)]
+        atomic: bool,
+
+        /// Only update these translated languages.
+        #[clap(long, value_delimiter = ',')]
+        languages: Vec<Lang>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+
+    /// Translate newline-delimited strings from stdin.
+    TranslateStdin {
         /// API key.
         #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
-        api_key: String,
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
 
-        /// Invalidate all keys.
+        /// Target language.
         #[clap(short, long)]
-        force: bool,
+        lang: Lang,
 
-        /// Invalidate specific keys.
+        /// Source language, detected when not set.
         #[clap(short, long)]
-        invalidate: Vec<String>,
+        source: Option<Lang>,
+    },
 
-        /// Directory of human-translated overrides.
+    /// Print account usage.
+    Usage {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+    },
+    /// Print account usage and supported languages.
+    Status {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+    },
+    /// Report which configured languages are supported targets.
+    ///
+    /// Languages are those translated in the arb-dir and any
+    /// languages given with --lang.
+    Supported {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Additional languages to check.
+        #[clap(short, long)]
+        lang: Vec<Lang>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// List language application resource bundles.
+    #[clap(alias = "ls")]
+    List {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Print the unique translatable strings in the template.
+    ///
+    /// Strings are printed one per line with backslashes and
+    /// newlines escaped unless JSON output is requested.
+    Extract {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Print a JSON array.
         #[clap(long)]
-        overrides: Option<PathBuf>,
+        json: bool,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Rename hyphenated language file names to use underscores.
+    Normalize {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
 
-        /// Translate and write to disc.
+        /// Rename files on disc.
         #[clap(long)]
         apply: bool,
 
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Export the cached source strings as an ARB per language.
+    CacheDump {
         /// File name prefix.
         #[clap(short, long)]
         name_prefix: Option<String>,
 
-        /// Target language.
+        /// Only export these languages.
         #[clap(short, long)]
-        lang: Lang,
+        lang: Vec<Lang>,
+
+        /// Write a file for each language to this directory.
+        #[clap(short, long)]
+        out_dir: Option<PathBuf>,
 
         /// Localization YAML file.
         file: PathBuf,
     },
-    /// Update existing translations.
-    #[clap(alias = "up")]
-    Update {
-        /// API key.
-        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
-        api_key: String,
+    /// Report translated values edited since they were last translated.
+    Drift {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Languages to check, defaults to all translated languages.
+        #[clap(short, long)]
+        lang: Vec<Lang>,
 
-        /// Invalidate all keys.
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Copy translated values edited by hand into the overrides.
+    Promote {
+        /// File name prefix.
         #[clap(short, long)]
-        force: bool,
+        name_prefix: Option<String>,
 
-        /// Invalidate specific keys.
+        /// Target language.
         #[clap(short, long)]
-        invalidate: Vec<String>,
+        lang: Lang,
 
         /// Directory of human-translated overrides.
         #[clap(long)]
         overrides: Option<PathBuf>,
 
-        /// Translate and write to disc.
+        /// Write the overrides file to disc.
         #[clap(long)]
         apply: bool,
 
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Remove cache entries for deleted languages.
+    Prune {
         /// File name prefix.
         #[clap(short, long)]
         name_prefix: Option<String>,
 
+        /// Write the cache to disc.
+        #[clap(long)]
+        apply: bool,
+
         /// Localization YAML file.
         file: PathBuf,
     },
-
-    /// Print account usage.
-    Usage {
-        /// API key.
-        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
-        api_key: String,
-    },
-    /// List language application resource bundles.
-    #[clap(alias = "ls")]
-    List {
+    /// Print the resolved configuration.
+    Config {
         /// File name prefix.
         #[clap(short, long)]
         name_prefix: Option<String>,
 
+        /// Directory of human-translated overrides.
+        #[clap(long)]
+        overrides: Option<PathBuf>,
+
         /// Localization YAML file.
         file: PathBuf,
     },
@@ -115,7 +461,10 @@ pub enum Command {
     Languages {
         /// API key.
         #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
-        api_key: String,
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
 
         /// Language type (source or target).
         #[clap(short, long, default_value = "source")]
@@ -131,18 +480,26 @@ pub enum Command {
         #[clap(short, long)]
         languages: Vec<Lang>,
 
+        /// Baseline file used to detect updates instead of the cache.
+        #[clap(long)]
+        cache: Option<PathBuf>,
+
+        /// List created and updated keys with their source text.
+        #[clap(short, long)]
+        verbose: bool,
+
         /// Localization YAML file.
         file: PathBuf,
     },
-    /// CSV comparison between template and a target language.
+    /// CSV comparison between template and target languages.
     Compare {
         /// File name prefix.
         #[clap(short, long)]
         name_prefix: Option<String>,
 
-        /// Target language.
+        /// Target languages, defaults to all translated languages.
         #[clap(short, long)]
-        lang: Lang,
+        lang: Vec<Lang>,
 
         /// Directory of human-translated overrides.
         #[clap(long)]
@@ -155,130 +512,392 @@ pub enum Command {
         /// Localization YAML file.
         file: PathBuf,
     },
-    /// Import CSV corrections to an overrides JSON file.
-    Import {
+    /// Export source, translation and description for review.
+    Review {
         /// File name prefix.
         #[clap(short, long)]
         name_prefix: Option<String>,
 
-        /// Column delimiter character.
-        #[clap(short, long, default_value = ",")]
-        delimiter: char,
-
         /// Target language.
         #[clap(short, long)]
         lang: Lang,
 
-        /// CSV comparison with corrections.
+        /// Output file for JSON document.
         #[clap(short, long)]
-        input: PathBuf,
+        output: Option<PathBuf>,
 
-        /// Directory of human-translated overrides.
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Report source character counts for each template key.
+    #[clap(alias = "chars")]
+    Characters {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Output format.
+        #[clap(long, value_enum, default_value_t)]
+        format: ReportFormat,
+
+        /// Output file for the report.
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Report placeholder usage for each template key.
+    Placeholders {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Only list keys with at least this many placeholders.
         #[clap(long)]
-        overrides: Option<PathBuf>,
+        min: Option<usize>,
 
         /// Localization YAML file.
         file: PathBuf,
     },
-}
+    /// Create a glossary from human-translated overrides.
+    Glossary {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
 
-#[tokio::main]
-pub async fn main() -> anyhow::Result<()> {
-    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
-        ))
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_target(false)
-                .without_time(),
-        )
-        .init();
+        #[clap(flatten)]
+        connection: ConnectionArgs,
 
-    let args = Arb::parse();
-    match args.cmd {
-        Command::Update {
-            file,
-            api_key,
-            name_prefix,
-            apply,
-            force,
-            invalidate,
-            overrides,
-        } => {
-            let mut intl = new_intl(&file, name_prefix.clone())?;
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
 
-            let overrides = overrides.or(intl.overrides_dir().map(PathBuf::from));
-            let overrides = if let Some(dir) = &overrides {
-                Some(intl.load_overrides(dir, None)?)
-            } else {
-                None
-            };
+        /// Target language.
+        #[clap(short, long)]
+        lang: Lang,
 
-            let translations = intl.list_translated()?;
-            for lang in translations.keys() {
-                if lang == intl.template_language() {
-                    continue;
-                }
-                translate_language(
-                    &mut intl,
-                    *lang,
-                    api_key.clone(),
-                    apply,
-                    force,
-                    invalidate.clone(),
-                    overrides.clone(),
-                )
-                .await?;
-            }
+        /// Directory of human-translated overrides.
+        #[clap(long)]
+        overrides: Option<PathBuf>,
 
-            if !apply {
-                tracing::warn!("dry run, use --apply to translate");
-            }
-        }
+        /// Name of the glossary.
+        #[clap(long)]
+        name: Option<String>,
 
-        Command::Translate {
-            lang,
-            file,
-            api_key,
-            name_prefix,
-            apply,
-            force,
-            invalidate,
-            overrides,
+        /// TSV or CSV file of term pairs to upload instead of overrides.
+        #[clap(long, conflicts_with = "overrides")]
+        entries: Option<PathBuf>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Translate key descriptions into translator notes.
+    Notes {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Target language.
+        #[clap(short, long)]
+        lang: Lang,
+
+        /// Write the notes to disc.
+        #[clap(long)]
+        apply: bool,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Translate a single key ignoring the diff.
+    TranslateKey {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Target language.
+        #[clap(short, long)]
+        lang: Lang,
+
+        /// Key to translate.
+        #[clap(short, long)]
+        key: String,
+
+        /// Directory of human-translated overrides.
+        #[clap(long)]
+        overrides: Option<PathBuf>,
+
+        /// Glossary identifier.
+        #[clap(long)]
+        glossary: Option<String>,
+
+        /// Context applied to the translation.
+        #[clap(long)]
+        context: Option<String>,
+
+        /// Markup handling for source strings (text or markdown).
+        #[clap(long, default_value = "text")]
+        markup: Markup,
+
+        /// Write the translation to disc.
+        #[clap(long)]
+        apply: bool,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Add keys that only exist in a translation to the template.
+    Backfill {
+        /// API key.
+        #[clap(short, long, hide_env_values = true, env = "DEEPL_API_KEY")]
+        api_key: Option<String>,
+
+        #[clap(flatten)]
+        connection: ConnectionArgs,
+
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Language with keys missing from the template.
+        #[clap(short, long)]
+        lang: Lang,
+
+        /// Translate and write the template to disc.
+        #[clap(long)]
+        apply: bool,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+    /// Import CSV corrections to an overrides JSON file.
+    Import {
+        /// File name prefix.
+        #[clap(short, long)]
+        name_prefix: Option<String>,
+
+        /// Column delimiter character.
+        #[clap(short, long, default_value = ",")]
+        delimiter: char,
+
+        /// Target language.
+        #[clap(short, long)]
+        lang: Lang,
+
+        /// CSV comparison with corrections.
+        ///
+        /// Corrections are read from the `Correction (<lang>)`
+        /// column so comparisons with several languages may be
+        /// imported one language at a time.
+        #[clap(short, long)]
+        input: PathBuf,
+
+        /// Directory of human-translated overrides.
+        #[clap(long)]
+        overrides: Option<PathBuf>,
+
+        /// Localization YAML file.
+        file: PathBuf,
+    },
+}
+
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
+        ))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_target(false)
+                .without_time(),
+        )
+        .init();
+
+    let args = Arb::parse();
+    match args.cmd {
+        Command::Update {
+            args,
+            atomic,
+            languages,
+            file,
         } => {
-            let mut intl = new_intl(&file, name_prefix.clone())?;
+            let mut intl = new_intl(&file, args.name_prefix.clone())?;
+            configure_intl(&mut intl, &args)?;
+            intl.set_defer_cache(atomic);
+            let overrides = load_overrides(&intl, args.overrides.as_ref(), None)?;
 
-            let overrides = overrides.or(intl.overrides_dir().map(PathBuf::from));
-            let overrides = if let Some(dir) = &overrides {
-                Some(intl.load_overrides(dir, None)?)
-            } else {
-                None
+            // Shared so the rate limit applies across languages
+            let api = translate_api(&args)?;
+            let mut pending = Vec::new();
+            let mut summaries = Vec::new();
+            let mut translations = intl.list_translated()?;
+            if !languages.is_empty() {
+                for lang in &languages {
+                    if !translations.contains_key(lang) {
+                        return Err(anyhow!(
+                            "'{}' is not a translated language in '{}'",
+                            lang,
+                            intl.arb_directory()?.display()
+                        ));
+                    }
+                }
+                translations.retain(|lang, _| languages.contains(lang));
+            }
+            if args.validate_lang {
+                let targets = translations
+                    .keys()
+                    .filter(|lang| *lang != intl.template_language())
+                    .copied()
+                    .collect::<Vec<_>>();
+                validate_targets(&api, &targets).await?;
+            }
+            for lang in translations.keys() {
+                if lang == intl.template_language() {
+                    continue;
+                }
+                let (summary, output) =
+                    translate_language(&api, &intl, (*lang).into(), &args, overrides.clone(), None)
+                        .await?;
+                summaries.push(summary);
+                if atomic {
+                    pending.extend(output);
+                } else {
+                    write_files(output)?;
+                }
+            }
+
+            if atomic && args.apply {
+                write_files(pending)?;
+                if summaries.iter().any(|summary| summary.cache_changed) {
+                    intl.flush_cache()?;
+                }
+            }
+
+            quota_warning(&api, &args, &mut summaries).await?;
+            write_report(&args, &summaries)?;
+
+            if args.json {
+                serde_json::to_writer_pretty(std::io::stdout(), &summaries)?;
+                println!();
+            }
+
+            if !args.apply {
+                tracing::warn!("dry run, use --apply to translate");
+            }
+        }
+
+        Command::Translate {
+            args,
+            lang,
+            code,
+            output,
+            file,
+        } => {
+            let mut intl = new_intl(&file, args.name_prefix.clone())?;
+            configure_intl(&mut intl, &args)?;
+            let overrides = load_overrides(&intl, args.overrides.as_ref(), None)?;
+            let targets: Vec<TargetLang> = match code {
+                Some(code) => vec![TargetLang::Code(code)],
+                None => lang.iter().map(|lang| (*lang).into()).collect(),
             };
+            if output.is_some() && targets.len() > 1 {
+                return Err(anyhow!("--output requires a single target language"));
+            }
+            // Shared so the rate limit applies across languages
+            let api = translate_api(&args)?;
+            if args.validate_lang && !lang.is_empty() {
+                validate_targets(&api, &lang).await?;
+            }
 
-            translate_language(
-                &mut intl, lang, api_key, apply, force, invalidate, overrides,
-            )
-            .await?;
+            let mut summaries = Vec::new();
+            for target in targets {
+                let (summary, output) = translate_language(
+                    &api,
+                    &intl,
+                    target,
+                    &args,
+                    overrides.clone(),
+                    output.as_deref(),
+                )
+                .await?;
+                write_files(output)?;
+                summaries.push(summary);
+            }
+            quota_warning(&api, &args, &mut summaries).await?;
+            write_report(&args, &summaries)?;
 
-            if !apply {
+            if args.json {
+                // Single language output is an object for compatibility
+                if let [summary] = summaries.as_slice() {
+                    serde_json::to_writer_pretty(std::io::stdout(), summary)?;
+                } else {
+                    serde_json::to_writer_pretty(std::io::stdout(), &summaries)?;
+                }
+                println!();
+            }
+
+            if !args.apply {
                 tracing::warn!("dry run, use --apply to translate");
             }
         }
-        Command::Usage { api_key } => {
-            let options = ApiOptions::new(api_key);
-            let api = DeeplApi::new(options);
+        Command::TranslateStdin {
+            api_key,
+            connection,
+            lang,
+            source,
+        } => {
+            let texts = std::io::stdin()
+                .lines()
+                .collect::<std::io::Result<Vec<_>>>()?;
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let translations = translate_strings(&api, &texts, lang.into(), source).await?;
+            for translation in translations {
+                println!("{}", translation);
+            }
+        }
+        Command::Usage {
+            api_key,
+            connection,
+        } => {
+            let api = new_api(api_key.as_deref(), &connection)?;
+            tracing::debug!(endpoint = %api.endpoint(), "usage");
             let usage = api.usage().await?;
             serde_json::to_writer_pretty(std::io::stdout(), &usage)?;
             println!();
         }
+        Command::Status {
+            api_key,
+            connection,
+        } => {
+            let api = new_api(api_key.as_deref(), &connection)?;
+            tracing::debug!(endpoint = %api.endpoint(), "status");
+            let status = api.account_status().await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &status)?;
+            println!();
+        }
         Command::Languages {
             api_key,
+            connection,
             language_type,
         } => {
-            let options = ApiOptions::new(api_key);
-            let api = DeeplApi::new(options);
+            let api = new_api(api_key.as_deref(), &connection)?;
             let langs = api.languages(language_type).await?;
             serde_json::to_writer_pretty(std::io::stdout(), &langs)?;
         }
@@ -286,98 +905,490 @@ pub async fn main() -> anyhow::Result<()> {
             name_prefix,
             file,
             languages,
+            cache,
+            verbose,
         } => {
             let mut output = BTreeMap::new();
+            let mut entries = BTreeMap::new();
             let intl = new_intl(file, name_prefix)?;
             let template = intl.template_content()?;
+            let baseline = if let Some(path) = &cache {
+                Some(intl.load_file(path)?)
+            } else {
+                None
+            };
             for lang in languages {
                 let lang_file = intl.load_or_default(lang)?;
-                let diff = template.diff(&lang_file, intl.cache().get_file(&lang));
-                output.insert(lang, diff);
+                let cache = intl.cache();
+                let cache = baseline.as_ref().or(cache.get_file(&lang));
+                let diff = template.diff(&lang_file, cache);
+                if verbose {
+                    entries.insert(lang, diff.entries(&template));
+                } else {
+                    output.insert(lang, diff);
+                }
+            }
+            if verbose {
+                serde_json::to_writer_pretty(std::io::stdout(), &entries)?;
+            } else {
+                serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+            }
+        }
+        Command::Supported {
+            api_key,
+            connection,
+            name_prefix,
+            lang,
+            file,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let mut languages = intl
+                .list_translated()?
+                .into_keys()
+                .filter(|lang| lang != intl.template_language())
+                .collect::<Vec<_>>();
+            for lang in lang {
+                if !languages.contains(&lang) {
+                    languages.push(lang);
+                }
+            }
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let sources = api.languages(LanguageType::Source).await?;
+            let targets = api.languages(LanguageType::Target).await?;
+            let mut output = LanguageSupport::default();
+            for lang in languages {
+                if targets.iter().any(|language| language.lang() == Some(lang)) {
+                    output.supported.push(lang);
+                } else if sources.iter().any(|language| language.lang() == Some(lang)) {
+                    output.source_only.push(lang);
+                } else {
+                    output.unsupported.push(lang);
+                }
             }
             serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+            println!();
         }
         Command::List { file, name_prefix } => {
             let intl = new_intl(file, name_prefix)?;
             let output = intl.list_translated()?;
             serde_json::to_writer_pretty(std::io::stdout(), &output)?;
         }
-        Command::Compare {
+        Command::Extract {
+            file,
+            name_prefix,
+            json,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let template = intl.template_content()?;
+            let mut sources: Vec<String> = Vec::new();
+            for entry in template.translatable_entries() {
+                if let Some(source) = entry.value().as_str() {
+                    if !sources.iter().any(|s| s == source) {
+                        sources.push(source.to_owned());
+                    }
+                }
+            }
+            if json {
+                serde_json::to_writer_pretty(std::io::stdout(), &sources)?;
+                println!();
+            } else {
+                for source in sources {
+                    println!("{}", source.replace('\\', "\\\\").replace('\n', "\\n"));
+                }
+            }
+        }
+        Command::Normalize {
+            file,
+            name_prefix,
+            apply,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let renames = intl.normalize_file_names(!apply)?;
+            serde_json::to_writer_pretty(std::io::stdout(), &renames)?;
+            println!();
+
+            if !apply {
+                tracing::warn!("dry run, use --apply to rename files");
+            }
+        }
+        Command::CacheDump {
             file,
             name_prefix,
-            output,
             lang,
-            overrides,
+            out_dir,
         } => {
             let intl = new_intl(file, name_prefix)?;
+            let cache = intl.cache();
+            let mut output = BTreeMap::new();
+            for (language, file) in cache.iter() {
+                if lang.is_empty() || lang.contains(language) {
+                    output.insert(*language, file);
+                }
+            }
 
+            if let Some(dir) = out_dir {
+                std::fs::create_dir_all(&dir)?;
+                for (language, file) in output {
+                    let path = dir.join(intl.format_file_name(language));
+                    tracing::info!(path = %path.display(), "write file");
+                    write_json(&path, file)?;
+                }
+            } else {
+                serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+                println!();
+            }
+        }
+        Command::Drift {
+            file,
+            name_prefix,
+            lang,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let languages = if lang.is_empty() {
+                intl.list_translated()?.into_keys().collect()
+            } else {
+                lang
+            };
+            let mut output = BTreeMap::new();
+            for lang in languages {
+                output.insert(lang, intl.drift(lang)?);
+            }
+            serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+            println!();
+        }
+        Command::Promote {
+            file,
+            name_prefix,
+            lang,
+            overrides,
+            apply,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
             let overrides = overrides.or(intl.overrides_dir().map(PathBuf::from));
-            let overrides = if let Some(dir) = &overrides {
-                Some(intl.load_overrides(dir, Some(vec![lang]))?)
+            let overrides = overrides.ok_or_else(|| {
+                anyhow!("no overrides, either configure overrides-dir or set --overrides")
+            })?;
+            let edits = intl.manual_edits(lang)?;
+            serde_json::to_writer_pretty(std::io::stdout(), &edits)?;
+            println!();
+
+            if apply {
+                if !edits.is_empty() {
+                    let mut overrides_map = intl.load_overrides(&overrides, Some(vec![lang]))?;
+                    let mut overrides_file = overrides_map.remove(&lang).unwrap_or_default();
+                    for entry in edits.entries() {
+                        if let Some(text) = entry.value().as_str() {
+                            overrides_file.insert_translation(entry.key(), text.to_owned());
+                        }
+                    }
+                    std::fs::create_dir_all(&overrides)?;
+                    let output_file = overrides.join(intl.format_file_name(lang));
+                    tracing::info!(path = %output_file.display(), "write file");
+                    write_json(&output_file, &overrides_file)?;
+                }
             } else {
-                None
+                tracing::warn!("dry run, use --apply to write overrides");
+            }
+        }
+        Command::Prune {
+            file,
+            name_prefix,
+            apply,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let removed = intl.prune_cache(!apply)?;
+            serde_json::to_writer_pretty(std::io::stdout(), &removed)?;
+            println!();
+
+            if !apply {
+                tracing::warn!("dry run, use --apply to write the cache");
+            }
+        }
+        Command::Config {
+            file,
+            name_prefix,
+            overrides,
+        } => {
+            let intl = new_intl(&file, name_prefix)?;
+            let config = ResolvedConfig {
+                file,
+                arb_dir: intl.arb_dir().to_owned(),
+                arb_directory: intl.arb_directory()?,
+                template_arb_file: intl.template_arb_file().to_owned(),
+                template_language: *intl.template_language(),
+                name_prefix: intl.name_prefix().to_owned(),
+                overrides_dir: overrides.or(intl.overrides_dir().map(PathBuf::from)),
+                cache_path: intl.cache_path()?,
             };
+            serde_json::to_writer_pretty(std::io::stdout(), &config)?;
+            println!();
+        }
+        Command::Compare {
+            file,
+            name_prefix,
+            output,
+            lang,
+            overrides,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
             let template_lang = intl.template_language();
-            let template = intl.template_content()?;
             let translated = intl.list_translated()?;
-            let mut rows: Vec<CsvRow> = Vec::new();
-            for (language, path) in translated {
-                if language != lang {
+            let languages = if lang.is_empty() {
+                translated
+                    .keys()
+                    .filter(|lang| *lang != template_lang)
+                    .copied()
+                    .collect::<Vec<_>>()
+            } else {
+                lang
+            };
+            let overrides = load_overrides(&intl, overrides.as_ref(), Some(languages.clone()))?;
+            let template = intl.template_content()?;
+
+            let mut files = Vec::new();
+            for language in &languages {
+                let path = translated
+                    .get(language)
+                    .ok_or_else(|| anyhow!("no translation file for '{}'", language))?;
+                files.push(intl.load_file(path)?);
+            }
+
+            let mut rows: Vec<CompareRow> = Vec::new();
+            for entry in template.translatable_entries() {
+                let key = entry.key().as_ref();
+                if !files.iter().any(|file| file.contains_key(key)) {
                     continue;
                 }
 
-                let contents = std::fs::read_to_string(path)?;
-                let file: ArbFile = serde_json::from_str(&contents)?;
-
-                for entry in template.entries() {
-                    if entry.is_translatable() {
-                        let correction = if let Some(overrides) = &overrides {
-                            if let Some(file) = overrides.get(&lang) {
-                                if let Some(entry) = file.lookup(entry.key().as_ref()) {
-                                    entry
-                                        .value()
-                                        .as_str()
-                                        .map(|s| s.to_string())
-                                        .unwrap_or_default()
-                                } else {
-                                    String::new()
-                                }
-                            } else {
-                                String::new()
-                            }
-                        } else {
-                            String::new()
-                        };
-
-                        if let Some(target) = file.lookup(entry.key().as_ref()) {
-                            rows.push(CsvRow {
-                                id: entry.key().as_ref().to_string(),
-                                source: entry
-                                    .value()
-                                    .as_str()
-                                    .map(|s| s.to_string())
-                                    .unwrap_or_default(),
-                                target: target
-                                    .value()
-                                    .as_str()
-                                    .map(|s| s.to_string())
-                                    .unwrap_or_default(),
-                                correction,
-                                comment: String::new(),
-                            });
-                        }
-                    }
-                }
+                let columns = languages
+                    .iter()
+                    .zip(files.iter())
+                    .map(|(language, file)| {
+                        let target = file
+                            .lookup(key)
+                            .and_then(|entry| entry.value().as_str().map(|s| s.to_string()))
+                            .unwrap_or_default();
+                        let correction = overrides
+                            .as_ref()
+                            .and_then(|overrides| overrides.get(language))
+                            .and_then(|file| file.lookup(key))
+                            .and_then(|entry| entry.value().as_str().map(|s| s.to_string()))
+                            .unwrap_or_default();
+                        (target, correction)
+                    })
+                    .collect();
+
+                rows.push(CompareRow {
+                    id: key.to_string(),
+                    source: entry
+                        .value()
+                        .as_str()
+                        .map(|s| s.to_string())
+                        .unwrap_or_default(),
+                    columns,
+                });
             }
 
             if let Some(path) = output {
                 let wtr = WriterBuilder::new().has_headers(false).from_path(path)?;
-                write_csv_rows(wtr, rows, *template_lang, lang)?;
+                write_compare_rows(wtr, rows, *template_lang, &languages)?;
             } else {
                 let wtr = WriterBuilder::new()
                     .has_headers(false)
                     .from_writer(std::io::stdout());
-                write_csv_rows(wtr, rows, *template_lang, lang)?;
+                write_compare_rows(wtr, rows, *template_lang, &languages)?;
+            }
+        }
+        Command::Review {
+            file,
+            name_prefix,
+            lang,
+            output,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let review = intl.export_review(lang)?;
+            if let Some(path) = output {
+                write_json(path, &review)?;
+            } else {
+                serde_json::to_writer_pretty(std::io::stdout(), &review)?;
+                println!();
+            }
+        }
+        Command::Characters {
+            file,
+            name_prefix,
+            format,
+            output,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let template = intl.template_content()?;
+            let mut rows: Vec<CharacterCount> = template
+                .translatable_entries()
+                .into_iter()
+                .map(|entry| CharacterCount {
+                    id: entry.key().to_string(),
+                    characters: entry
+                        .value()
+                        .as_str()
+                        .map(|s| s.chars().count())
+                        .unwrap_or_default(),
+                })
+                .collect();
+            rows.sort_by_key(|row| std::cmp::Reverse(row.characters));
+
+            match (format, output) {
+                (ReportFormat::Json, Some(path)) => {
+                    write_json(path, &rows)?;
+                }
+                (ReportFormat::Json, None) => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &rows)?;
+                    println!();
+                }
+                (ReportFormat::Csv, Some(path)) => {
+                    write_character_counts(WriterBuilder::new().from_path(path)?, rows)?;
+                }
+                (ReportFormat::Csv, None) => {
+                    write_character_counts(
+                        WriterBuilder::new().from_writer(std::io::stdout()),
+                        rows,
+                    )?;
+                }
+            }
+        }
+        Command::Placeholders {
+            file,
+            name_prefix,
+            min,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let mut stats = intl.template_content()?.placeholder_stats();
+            if let Some(min) = min {
+                stats.keys.retain(|_, names| names.len() >= min);
+            }
+            serde_json::to_writer_pretty(std::io::stdout(), &stats)?;
+            println!();
+        }
+        Command::Glossary {
+            api_key,
+            connection,
+            name_prefix,
+            lang,
+            overrides,
+            name,
+            entries,
+            file,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let name = name.unwrap_or_else(|| intl.format_file_name(lang));
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let glossary = if let Some(path) = entries {
+                api.create_glossary_from_tsv(name, *intl.template_language(), lang, path)
+                    .await?
+            } else {
+                let overrides = load_overrides(&intl, overrides.as_ref(), Some(vec![lang]))?
+                    .ok_or_else(|| {
+                        anyhow!("no overrides, either configure overrides-dir or set --overrides")
+                    })?;
+                let overrides_file = overrides
+                    .get(&lang)
+                    .ok_or_else(|| anyhow!("no overrides for language '{}'", lang))?;
+                intl.create_glossary(&api, name, lang, overrides_file)
+                    .await?
+            };
+            serde_json::to_writer_pretty(std::io::stdout(), &glossary)?;
+            println!();
+        }
+        Command::Notes {
+            api_key,
+            connection,
+            name_prefix,
+            lang,
+            apply,
+            file,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let translated = intl.translate_descriptions(&api, lang, !apply).await?;
+            if apply {
+                let mut output = Vec::new();
+                for (file_path, file) in intl.split_translation(&lang.to_string(), &translated)? {
+                    output.push((file_path, serde_json::to_string_pretty(&file)?));
+                }
+                write_files(output)?;
+            } else {
+                tracing::warn!("dry run, use --apply to translate descriptions");
+            }
+        }
+        Command::TranslateKey {
+            api_key,
+            connection,
+            name_prefix,
+            lang,
+            key,
+            overrides,
+            glossary,
+            context,
+            markup,
+            apply,
+            file,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let mut options = TranslationOptions::new(lang);
+            options.overrides = load_overrides(&intl, overrides.as_ref(), Some(vec![lang]))?;
+            options.glossary_id = glossary;
+            options.global_context = context;
+            options.markup = markup;
+            let translation = intl.translate_key(&api, options, &key).await?;
+            println!("{}", translation);
+            if apply {
+                let template = intl.template_content()?;
+                let arb_key = ArbKey::new(&key);
+                let mut translated = intl.load_or_default(lang)?;
+                match template.index_of(&key) {
+                    Some(index) if !translated.contains_key(&key) && index < translated.len() => {
+                        translated.shift_insert_translation(index, &arb_key, translation.clone())
+                    }
+                    _ => translated.insert_translation(&arb_key, translation.clone()),
+                }
+                let mut output = Vec::new();
+                for (file_path, file) in intl.split_translation(&lang.to_string(), &translated)? {
+                    output.push((file_path, serde_json::to_string_pretty(&file)?));
+                }
+                write_files(output)?;
+
+                if let Some(entry) = template.lookup(&key) {
+                    let mut cache = intl.cache();
+                    cache.add_entry(lang, entry);
+                    cache.add_translation(lang, &arb_key, translation);
+                }
+                intl.flush_cache()?;
+            } else {
+                tracing::warn!("dry run, use --apply to write the translation");
+            }
+        }
+        Command::Backfill {
+            api_key,
+            connection,
+            name_prefix,
+            lang,
+            apply,
+            file,
+        } => {
+            let intl = new_intl(file, name_prefix)?;
+            let api = new_api(api_key.as_deref(), &connection)?;
+            let proposed = intl.backfill(&api, lang, !apply).await?;
+            if apply {
+                if !proposed.is_empty() {
+                    let path = intl.template_path()?;
+                    let mut template = intl.load_file(&path)?;
+                    template.extend(proposed);
+                    write_files(vec![(path, serde_json::to_string_pretty(&template)?)])?;
+                }
+            } else {
+                serde_json::to_writer_pretty(std::io::stdout(), &proposed)?;
+                println!();
+                tracing::warn!("dry run, use --apply to translate and update the template");
             }
         }
         Command::Import {
@@ -396,22 +1407,31 @@ pub async fn main() -> anyhow::Result<()> {
             })?;
             let mut overrides_map = intl.load_overrides(&overrides, Some(vec![lang]))?;
             let mut default = ArbFile::default();
-            let overrides_file = overrides_map.get_mut(&lang).unwrap_or_else(|| &mut default);
+            let overrides_file = overrides_map.get_mut(&lang).unwrap_or(&mut default);
             let mut rdr = ReaderBuilder::new()
                 .delimiter(delimiter as u8)
-                .from_path(input)?;
-            for result in rdr.deserialize() {
-                // Our headers don't match the field names!
-                let record: (String, String, String, String, String) = result?;
-                let record = CsvRow {
-                    id: record.0,
-                    source: record.1,
-                    target: record.2,
-                    correction: record.3,
-                    comment: record.4,
-                };
-                if !record.correction.is_empty() {
-                    overrides_file.insert_translation(&ArbKey::new(&record.id), record.correction);
+                .from_path(&input)?;
+
+            // Columns are found by name so comparisons with
+            // several languages can be imported
+            let headers = rdr.headers()?.clone();
+            let id_column = headers
+                .iter()
+                .position(|header| header == "Identifier")
+                .unwrap_or(0);
+            let correction_header = format!("Correction ({})", lang);
+            let correction_column = headers
+                .iter()
+                .position(|header| header == correction_header || header == "Correction")
+                .ok_or_else(|| {
+                    anyhow!("no '{}' column in '{}'", correction_header, input.display())
+                })?;
+            for result in rdr.records() {
+                let record = result?;
+                let id = record.get(id_column).unwrap_or_default();
+                let correction = record.get(correction_column).unwrap_or_default();
+                if !correction.is_empty() {
+                    overrides_file.insert_translation(&ArbKey::new(id), correction.to_owned());
                 }
             }
 
@@ -419,69 +1439,399 @@ pub async fn main() -> anyhow::Result<()> {
             let output_file = overrides.join(output_name);
 
             tracing::info!(path = %output_file.display(), "write file");
-            serde_json::to_writer_pretty(std::fs::File::create(&output_file)?, &overrides_file)?;
+            write_json(&output_file, &overrides_file)?;
         }
     }
     Ok(())
 }
 
+fn api_options(api_key: Option<&str>, connection: &ConnectionArgs) -> Result<ApiOptions> {
+    let api_key = match api_key {
+        Some(api_key) => api_key.to_owned(),
+        None => read_config_api_key()?.ok_or_else(|| {
+            anyhow!("no API key, set --api-key, DEEPL_API_KEY or add a DeepL config file")
+        })?,
+    };
+    let mut options =
+        ApiOptions::new(api_key).with_user_agent(concat!("arb/", env!("CARGO_PKG_VERSION")));
+    if let Some(endpoint) = &connection.endpoint {
+        options = options.with_endpoint(endpoint.parse()?);
+    }
+    if let Some(timeout) = connection.timeout {
+        options = options.timeout(std::time::Duration::from_secs(timeout));
+    }
+    if let Some(max_retries) = connection.max_retries {
+        options = options.max_retries(max_retries);
+    }
+    if let Some(api_version) = &connection.api_version {
+        options = options.with_api_version(api_version);
+    }
+    Ok(options)
+}
+
+/// Read the API key from a DeepL config file.
+///
+/// Looks for `$XDG_CONFIG_HOME/deepl/config` (defaults to
+/// `~/.config/deepl/config`) and then `~/.deeplrc`; files may
+/// contain the bare key or an `auth_key = <key>` line.
+fn read_config_api_key() -> Result<Option<String>> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+    let candidates = [
+        config_dir.map(|dir| dir.join("deepl").join("config")),
+        home.map(|home| home.join(".deeplrc")),
+    ];
+    for path in candidates.into_iter().flatten() {
+        if !path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)?;
+        if let Some(api_key) = parse_config_api_key(&content) {
+            tracing::debug!(path = %path.display(), "api key from config");
+            return Ok(Some(api_key));
+        }
+    }
+    Ok(None)
+}
+
+fn new_api(api_key: Option<&str>, connection: &ConnectionArgs) -> Result<DeeplApi> {
+    Ok(DeeplApi::new(api_options(api_key, connection)?))
+}
+
+/// Create the API client for the translate commands.
+fn translate_api(args: &TranslateArgs) -> Result<DeeplApi> {
+    let mut api_options = api_options(args.api_key.as_deref(), &args.connection)?;
+    if let Some(rate_limit) = args.rate_limit {
+        api_options = api_options.rate_limit(rate_limit);
+    }
+    Ok(DeeplApi::new(api_options))
+}
+
 fn new_intl(path: impl AsRef<Path>, name_prefix: Option<String>) -> Result<Intl> {
     Ok(Intl::new_with_prefix(path, name_prefix)?)
 }
 
+fn configure_intl(intl: &mut Intl, args: &TranslateArgs) -> Result<()> {
+    intl.set_output_dir(args.out_dir.clone());
+    intl.set_strict(args.strict);
+    if let Some(format) = args.locale_format {
+        intl.set_locale_format(format);
+    }
+    // Only create the output directory when writing translations
+    if args.apply {
+        intl.ensure_output_directory()?;
+    }
+    Ok(())
+}
+
+fn load_overrides(
+    intl: &Intl,
+    overrides: Option<&PathBuf>,
+    languages: Option<Vec<Lang>>,
+) -> Result<Option<HashMap<Lang, ArbFile>>> {
+    let overrides = overrides
+        .cloned()
+        .or(intl.overrides_dir().map(PathBuf::from));
+    Ok(if let Some(dir) = &overrides {
+        Some(intl.load_overrides(dir, languages)?)
+    } else {
+        None
+    })
+}
+
+/// Ensure the target languages are supported by the API.
+async fn validate_targets(api: &DeeplApi, languages: &[Lang]) -> Result<()> {
+    let supported = api.languages(LanguageType::Target).await?;
+    for lang in languages {
+        if !supported
+            .iter()
+            .any(|language| language.lang() == Some(*lang))
+        {
+            let valid = supported
+                .iter()
+                .map(|language| language.language.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(anyhow!(
+                "'{}' is not a supported target language, valid targets are: {}",
+                lang,
+                valid
+            ));
+        }
+    }
+    Ok(())
+}
+
 async fn translate_language(
-    intl: &mut Intl,
-    lang: Lang,
-    api_key: String,
-    apply: bool,
-    force: bool,
-    invalidate: Vec<String>,
+    api: &DeeplApi,
+    intl: &Intl,
+    target: TargetLang,
+    args: &TranslateArgs,
     overrides: Option<HashMap<Lang, ArbFile>>,
-) -> Result<()> {
-    let invalidation = if force {
+    output: Option<&Path>,
+) -> Result<(TranslateSummary, Vec<(PathBuf, String)>)> {
+    let invalidation = if args.force {
         Some(Invalidation::All)
-    } else if !invalidate.is_empty() {
-        Some(Invalidation::Keys(invalidate))
+    } else if !args.invalidate.is_empty() {
+        Some(Invalidation::Keys(args.invalidate.clone()))
+    } else {
+        None
+    };
+
+    let (lang, target_code) = match &target {
+        TargetLang::Lang(lang) => (*lang, None),
+        TargetLang::Code(code) => (*intl.template_language(), Some(code.to_owned())),
+    };
+
+    let metadata_filter = if !args.metadata_allow.is_empty() {
+        Some(MetadataFilter::Allow(args.metadata_allow.clone()))
+    } else if !args.metadata_deny.is_empty() {
+        Some(MetadataFilter::Deny(args.metadata_deny.clone()))
     } else {
         None
     };
 
-    let api = DeeplApi::new(ApiOptions::new(api_key));
+    let baseline = if let Some(path) = &args.baseline {
+        Some(intl.load_file(path)?)
+    } else {
+        None
+    };
+
+    // Changes are detected against the explicit output file
+    let existing = match output {
+        Some(path) if path.try_exists()? => Some(intl.load_file(path)?),
+        Some(_) => Some(ArbFile::default()),
+        None => None,
+    };
+
     let options = TranslationOptions {
         target_lang: lang,
-        dry_run: !apply,
+        dry_run: !args.apply,
         invalidation,
         overrides,
+        post_process: None,
+        glossary_id: args.glossary.clone(),
+        global_context: args.context.clone(),
+        markup: args.markup,
+        collect_errors: args.collect_errors,
+        target_code,
+        formality: None,
+        source_hash: args.source_hash,
+        character_budget: args.character_budget,
+        metadata_filter,
+        keep_on_empty: true,
+        retry_preserve_formatting: !args.no_retry_preserve_formatting,
+        output_order: args.output_order,
+        baseline,
+        placeholder_strategy: args.placeholder_strategy,
+        ignore_whitespace: args.ignore_whitespace,
+        existing,
         disable_cache: false,
     };
 
-    let result = intl.translate(&api, options).await?;
+    let result = intl.translate(api, options).await?;
+
+    for (key, error) in &result.errors {
+        tracing::error!(lang = %target, key = %key, error = %error, "skipped");
+    }
 
-    if apply {
-        let content = serde_json::to_string_pretty(&result.translated)?;
-        let file_path = intl.file_path(lang)?;
+    let mut warnings = result.warnings.clone();
+    for path in intl.skipped_files()? {
+        tracing::warn!(path = %path.display(), "skipped file");
+        warnings.push(Warning::SkippedFile { path });
+    }
+
+    let sorted = |keys: &std::collections::HashSet<String>| {
+        let mut keys = keys.iter().cloned().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    };
+    let plan = LanguagePlan {
+        create: sorted(&result.diff.create),
+        update: sorted(&result.diff.update),
+        delete: sorted(&result.diff.delete),
+        total: result.diff.create.len() + result.diff.update.len() + result.diff.delete.len(),
+    };
+    let summary = TranslateSummary {
+        lang: target.to_string(),
+        translated: result.length,
+        skipped: result.errors.iter().map(|(key, _)| key.clone()).collect(),
+        overridden: result.overridden.clone(),
+        deleted: result.deleted.clone(),
+        characters: result.characters,
+        deferred: result.deferred.clone(),
+        skipped_keys: result.skipped.clone(),
+        warnings,
+        changed: result.changed,
+        plan,
+        cache_changed: result.cache_changed,
+    };
+    tracing::info!(
+        lang = %summary.lang,
+        translated = %summary.translated,
+        skipped = %summary.skipped.len(),
+        overridden = %summary.overridden.len(),
+        deleted = %summary.deleted.len(),
+        characters = %summary.characters,
+        deferred = %summary.deferred.len(),
+        "summary");
+
+    let is_template = target.is_lang(*intl.template_language());
+    if args.patch && !is_template {
+        for (file_path, content) in translation_files(intl, &target, &result.translated, output)? {
+            let current = if file_path.try_exists()? {
+                serde_json::to_string_pretty(&intl.load_file(&file_path)?)?
+            } else {
+                String::new()
+            };
+            print!(
+                "{}",
+                unified_diff(&file_path.display().to_string(), &current, &content)
+            );
+        }
+    }
+
+    // Never overwrite the template file
+    let changed = result.changed || args.out_dir.is_some();
+    if args.apply && changed && !is_template {
+        let mut files = translation_files(intl, &target, &result.translated, output)?;
+        if args.metadata_sidecar {
+            // Main translation file is always first
+            if let Some((file_path, _)) = files.first() {
+                let sidecar = file_path.with_extension("metadata.json");
+                files.push((sidecar, serde_json::to_string_pretty(&result.provenance)?));
+            }
+        }
+        return Ok((summary, files));
+    }
+    Ok((summary, Vec::new()))
+}
+
+/// Add a warning to each summary once 90% of the character
+/// limit has been used.
+///
+/// Usage is for the account so it is only fetched once per run.
+async fn quota_warning(
+    api: &DeeplApi,
+    args: &TranslateArgs,
+    summaries: &mut [TranslateSummary],
+) -> Result<()> {
+    if !args.json || !args.apply {
+        return Ok(());
+    }
+    let usage = api.usage().await?;
+    if usage.character_limit > 0 && usage.character_count * 10 >= usage.character_limit * 9 {
+        let warning = Warning::QuotaProximity {
+            used: usage.character_count,
+            limit: usage.character_limit,
+        };
+        for summary in summaries {
+            summary.warnings.push(warning.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Serialize a translation to the file for the language or
+/// the output path.
+fn translation_files(
+    intl: &Intl,
+    target: &TargetLang,
+    translated: &ArbFile,
+    output: Option<&Path>,
+) -> Result<Vec<(PathBuf, String)>> {
+    let mut files = Vec::new();
+    if let Some(path) = output {
+        files.push((path.to_owned(), serde_json::to_string_pretty(translated)?));
+    } else {
+        for (file_path, file) in intl.split_translation(&target.to_string(), translated)? {
+            files.push((file_path, serde_json::to_string_pretty(&file)?));
+        }
+    }
+    Ok(files)
+}
+
+/// Write the plan for each language to the report file.
+fn write_report(args: &TranslateArgs, summaries: &[TranslateSummary]) -> Result<()> {
+    if let Some(path) = &args.report {
+        let report = summaries
+            .iter()
+            .map(|summary| (&summary.lang, &summary.plan))
+            .collect::<BTreeMap<_, _>>();
+        tracing::info!(path = %path.display(), "write report");
+        write_json(path, &report)?;
+    }
+    Ok(())
+}
+
+/// Write a value as pretty JSON ending with a newline.
+fn write_json(path: impl AsRef<Path>, value: &impl Serialize) -> Result<()> {
+    let mut content = serde_json::to_string_pretty(value)?;
+    content.push('\n');
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Write translated files.
+///
+/// Content is written to temporary files first and only
+/// renamed into place once every file was written so a
+/// failure never leaves a partial set of files. Each file
+/// ends with a single trailing newline.
+fn write_files(files: Vec<(PathBuf, String)>) -> Result<()> {
+    let mut staged = Vec::new();
+    for (file_path, content) in files {
+        let content = format!("{}\n", content.trim_end_matches('\n'));
+        let temp_path = file_path.with_extension("arb.tmp");
+        if let Err(e) = std::fs::write(&temp_path, &content) {
+            for (temp_path, _) in &staged {
+                let _ = std::fs::remove_file(temp_path);
+            }
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        staged.push((temp_path, file_path));
+    }
+
+    for (temp_path, file_path) in staged {
         tracing::info!(path = %file_path.display(), "write file");
-        std::fs::write(&file_path, &content)?;
+        std::fs::rename(&temp_path, &file_path)?;
     }
     Ok(())
 }
 
-fn write_csv_rows<W: std::io::Write>(
+fn write_compare_rows<W: std::io::Write>(
     mut wtr: Writer<W>,
-    rows: Vec<CsvRow>,
+    rows: Vec<CompareRow>,
     source: Lang,
-    target: Lang,
+    targets: &[Lang],
+) -> Result<()> {
+    let mut headers = vec!["Identifier".to_string(), format!("Source ({})", source)];
+    for target in targets {
+        headers.push(format!("Target ({})", target));
+        headers.push(format!("Correction ({})", target));
+    }
+    headers.push("Comment".to_string());
+    wtr.write_record(&headers)?;
+    for row in rows {
+        let mut record = vec![row.id, row.source];
+        for (target, correction) in row.columns {
+            record.push(target);
+            record.push(correction);
+        }
+        record.push(String::new());
+        wtr.write_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+fn write_character_counts<W: std::io::Write>(
+    mut wtr: Writer<W>,
+    rows: Vec<CharacterCount>,
 ) -> Result<()> {
-    let source_header = format!("Source ({})", source);
-    let target_header = format!("Target ({})", target);
-    let correction_header = format!("Correction ({})", target);
-    wtr.write_record(&[
-        "Identifier",
-        &source_header,
-        &target_header,
-        &correction_header,
-        "Comment",
-    ])?;
     for row in rows {
         wtr.serialize(&row)?;
     }
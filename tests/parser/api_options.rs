@@ -0,0 +1,60 @@
+use arb_lib::deepl::{ApiOptions, DeeplApi, Plan};
+use std::time::{Duration, Instant};
+
+#[test]
+pub fn api_version() {
+    let options = ApiOptions::new("not-used");
+    assert_eq!("v2", options.api_version());
+
+    let options = options.with_api_version("/mock/v3/");
+    assert_eq!("mock/v3", options.api_version());
+}
+
+#[test]
+pub fn user_agent() {
+    let options = ApiOptions::new("not-used");
+    assert!(options.user_agent().starts_with("deepl-pro/"));
+
+    let options = options.with_user_agent("arb/1.0.0");
+    assert_eq!("arb/1.0.0", options.user_agent());
+}
+
+#[test]
+pub fn connection_settings() {
+    // Client is built from every connection setting
+    let options = ApiOptions::new("not-used")
+        .pool_max_idle_per_host(4)
+        .http2_prior_knowledge()
+        .tcp_keepalive(Duration::from_secs(30))
+        .timeout(Duration::from_secs(5));
+    let api = DeeplApi::new(options);
+    assert_eq!(Plan::Pro, api.plan());
+}
+
+#[tokio::test]
+pub async fn rate_limit() {
+    // Requests fail to connect but are still paced
+    let options = ApiOptions::new("not-used")
+        .with_endpoint("http://127.0.0.1:1/".parse().unwrap())
+        .rate_limit(20.0);
+    let api = DeeplApi::new(options);
+    let start = Instant::now();
+    for _ in 0..3 {
+        assert!(api.usage().await.is_err());
+    }
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+pub fn plan() {
+    let options = ApiOptions::new("not-used:fx");
+    assert_eq!(Plan::Free, options.plan());
+
+    // Custom endpoints keep the plan of the key
+    let options = options.with_endpoint("http://127.0.0.1:1/".parse().unwrap());
+    assert_eq!(Plan::Free, options.plan());
+
+    let options =
+        ApiOptions::new("not-used").with_endpoint("https://api-free.deepl.com".parse().unwrap());
+    assert_eq!(Plan::Pro, options.plan());
+}
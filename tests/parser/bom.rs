@@ -0,0 +1,16 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl};
+
+#[test]
+pub fn byte_order_mark() -> Result<()> {
+    let index = Intl::new("tests/fixtures/bom.yaml")?;
+
+    let template = index.template_content()?;
+    let value = template.lookup("message").unwrap();
+    assert_eq!(Some("Hello"), value.value().as_str());
+
+    let french = index.load(Lang::Fr)?;
+    let value = french.lookup("message").unwrap();
+    assert_eq!(Some("Bonjour"), value.value().as_str());
+    Ok(())
+}
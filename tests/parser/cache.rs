@@ -0,0 +1,68 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, ArbCache, ArbEntry};
+use serde_json::Value;
+
+#[test]
+pub fn cache_retain_languages() -> Result<()> {
+    let value = Value::String("Hello".to_string());
+    let mut cache = ArbCache::default();
+    cache.add_entry(Lang::Fr, ArbEntry::new("message", &value));
+    cache.add_entry(Lang::De, ArbEntry::new("message", &value));
+
+    let removed = cache.retain_languages(&[Lang::Fr]);
+    assert_eq!(vec![Lang::De], removed);
+    assert!(cache.get_file(&Lang::Fr).is_some());
+    assert!(cache.get_file(&Lang::De).is_none());
+
+    Ok(())
+}
+
+#[test]
+pub fn cache_iter() -> Result<()> {
+    let value = Value::String("Hello".to_string());
+    let mut cache = ArbCache::default();
+    cache.add_entry(Lang::Fr, ArbEntry::new("message", &value));
+    cache.add_entry(Lang::De, ArbEntry::new("message", &value));
+
+    let languages = cache.languages().copied().collect::<Vec<_>>();
+    assert_eq!(vec![Lang::De, Lang::Fr], languages);
+    for (_, file) in cache.iter() {
+        assert!(file.contains_key("message"));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn cache_merge() -> Result<()> {
+    let hello = Value::String("Hello".to_string());
+    let world = Value::String("World".to_string());
+    let mut cache = ArbCache::default();
+    cache.add_entry(Lang::Fr, ArbEntry::new("message", &hello));
+    cache.add_entry(Lang::Fr, ArbEntry::new("title", &hello));
+
+    let mut other = ArbCache::default();
+    other.add_entry(Lang::Fr, ArbEntry::new("message", &world));
+    other.add_entry(Lang::De, ArbEntry::new("message", &world));
+
+    cache.merge(other);
+
+    let languages = cache.languages().copied().collect::<Vec<_>>();
+    assert_eq!(vec![Lang::De, Lang::Fr], languages);
+    let french = cache.get_file(&Lang::Fr).unwrap();
+    assert_eq!(
+        Some("World"),
+        french.lookup("message").unwrap().value().as_str()
+    );
+    assert_eq!(
+        Some("Hello"),
+        french.lookup("title").unwrap().value().as_str()
+    );
+    let german = cache.get_file(&Lang::De).unwrap();
+    assert_eq!(
+        Some("World"),
+        german.lookup("message").unwrap().value().as_str()
+    );
+
+    Ok(())
+}
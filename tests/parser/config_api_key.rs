@@ -0,0 +1,35 @@
+use arb_lib::deepl::parse_config_api_key;
+
+#[test]
+pub fn config_api_key_bare() {
+    assert_eq!(
+        Some("abcd:fx".to_owned()),
+        parse_config_api_key("abcd:fx\n")
+    );
+    assert_eq!(
+        Some("abcd:fx".to_owned()),
+        parse_config_api_key("# DeepL\n\n  abcd:fx  \n")
+    );
+}
+
+#[test]
+pub fn config_api_key_auth_key() {
+    assert_eq!(
+        Some("abcd:fx".to_owned()),
+        parse_config_api_key("[auth]\nauth_key = \"abcd:fx\"\n")
+    );
+    assert_eq!(
+        Some("a=b".to_owned()),
+        parse_config_api_key("; comment\nauth-key=a=b\n")
+    );
+    assert_eq!(
+        Some("abcd".to_owned()),
+        parse_config_api_key("endpoint = https://api.deepl.com\nauth_key = abcd\n")
+    );
+}
+
+#[test]
+pub fn config_api_key_missing() {
+    assert_eq!(None, parse_config_api_key("[auth]\n# auth_key = abcd\n"));
+    assert_eq!(None, parse_config_api_key("auth_key =\n"));
+}
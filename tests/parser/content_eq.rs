@@ -0,0 +1,17 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn content_eq() -> Result<()> {
+    let lhs: ArbFile = serde_json::from_str(r#"{"a": "A", "@a": {"x": 1, "y": 2}, "b": "B"}"#)?;
+    let rhs: ArbFile = serde_json::from_str(r#"{"b": "B", "a": "A", "@a": {"y": 2, "x": 1}}"#)?;
+    assert!(lhs.content_eq(&rhs));
+
+    let other: ArbFile = serde_json::from_str(r#"{"a": "A", "@a": {"x": 1, "y": 2}}"#)?;
+    assert!(!lhs.content_eq(&other));
+    assert!(!other.content_eq(&lhs));
+
+    let other: ArbFile = serde_json::from_str(r#"{"a": "A", "@a": {"x": 1, "y": 2}, "b": "C"}"#)?;
+    assert!(!lhs.content_eq(&other));
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn description() -> Result<()> {
+    let mut file: ArbFile = serde_json::from_str(
+        r#"{
+  "greeting": "Hello",
+  "@greeting": {
+    "description": "Greeting"
+  },
+  "farewell": "Goodbye",
+  "last": "Last"
+}"#,
+    )?;
+
+    assert_eq!(Some("Greeting"), file.description("greeting"));
+    assert_eq!(None, file.description("farewell"));
+
+    file.set_description("greeting", "Home screen greeting");
+    assert_eq!(Some("Home screen greeting"), file.description("greeting"));
+
+    file.set_description("farewell", "Shown on sign out");
+    assert_eq!(Some("Shown on sign out"), file.description("farewell"));
+    assert_eq!(Some(3), file.index_of("@farewell"));
+    Ok(())
+}
+
+#[test]
+pub fn translator_note() -> Result<()> {
+    let mut file: ArbFile = serde_json::from_str(
+        r#"{
+  "greeting": "Bonjour"
+}"#,
+    )?;
+    assert_eq!(None, file.translator_note("greeting", "FR"));
+    file.set_translator_note("greeting", "FR", "Salutation");
+    assert_eq!(Some("Salutation"), file.translator_note("greeting", "fr"));
+    let value = serde_json::to_value(&file)?;
+    assert_eq!(
+        "Salutation",
+        value["@greeting"]["x-translator-note-fr"].as_str().unwrap()
+    );
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use arb_lib::{deepl::Lang, Intl};
+use arb_lib::{deepl::Lang, ArbFile, DiffStatus, FileDiff, Intl};
 
 #[test]
 pub fn diff_create() -> Result<()> {
@@ -40,3 +40,111 @@ pub fn diff_delete() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+pub fn diff_entries() -> Result<()> {
+    let index = Intl::new("tests/fixtures/diff_update.yaml")?;
+
+    let template = index.template_content()?;
+    let french = index.load(Lang::Fr)?;
+
+    let diff = template.diff(&french, index.cache().get_file(&Lang::Fr));
+    let entries = diff.entries(&template);
+    let entry = entries.iter().find(|x| x.key == "message").unwrap();
+    assert_eq!(DiffStatus::Update, entry.status);
+    assert_eq!(
+        template.lookup("message").unwrap().value().as_str(),
+        entry.source.as_deref()
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn diff_track_description() -> Result<()> {
+    let template: ArbFile = serde_json::from_str(
+        r#"{
+  "tracked": "Save",
+  "@tracked": {
+    "description": "Button to save a document",
+    "x-track-description": true
+  },
+  "untracked": "Open",
+  "@untracked": {
+    "description": "Button to open a document"
+  }
+}"#,
+    )?;
+    let french: ArbFile = serde_json::from_str(
+        r#"{
+  "tracked": "Enregistrer",
+  "untracked": "Ouvrir"
+}"#,
+    )?;
+    let cache: ArbFile = serde_json::from_str(
+        r#"{
+  "tracked": "Save",
+  "@tracked": {
+    "description": "Save"
+  },
+  "untracked": "Open",
+  "@untracked": {
+    "description": "Open"
+  }
+}"#,
+    )?;
+
+    let diff = template.diff(&french, Some(&cache));
+    assert!(diff.update.contains("tracked"));
+    assert!(!diff.update.contains("untracked"));
+    assert_eq!(1, diff.update.len());
+
+    Ok(())
+}
+
+#[test]
+pub fn diff_serialize_sorted() -> Result<()> {
+    let mut diff = FileDiff::default();
+    for key in ["zeta", "alpha", "mu", "beta"] {
+        diff.create.insert(key.to_owned());
+    }
+    let value = serde_json::to_value(&diff)?;
+    assert_eq!(
+        serde_json::json!(["alpha", "beta", "mu", "zeta"]),
+        value["create"]
+    );
+    Ok(())
+}
+
+#[test]
+pub fn diff_ignore_whitespace() -> Result<()> {
+    let template: ArbFile = serde_json::from_str(
+        r#"{
+  "trailing": "Save ",
+  "collapsed": "Open  the   file",
+  "changed": "Close the file"
+}"#,
+    )?;
+    let french: ArbFile = serde_json::from_str(
+        r#"{
+  "trailing": "Enregistrer",
+  "collapsed": "Ouvrir le fichier",
+  "changed": "Fermer"
+}"#,
+    )?;
+    let cache: ArbFile = serde_json::from_str(
+        r#"{
+  "trailing": "Save",
+  "collapsed": "Open the file",
+  "changed": "Close"
+}"#,
+    )?;
+
+    let diff = template.diff(&french, Some(&cache));
+    assert_eq!(3, diff.update.len());
+
+    let diff = template.diff_ignore_whitespace(&french, Some(&cache));
+    assert_eq!(1, diff.update.len());
+    assert!(diff.update.contains("changed"));
+    Ok(())
+}
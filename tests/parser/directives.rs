@@ -0,0 +1,29 @@
+use anyhow::Result;
+use arb_lib::{deepl::Formality, ArbFile, Error, Intl};
+
+#[test]
+pub fn directives() -> Result<()> {
+    let index = Intl::new("tests/fixtures/directives.yaml")?;
+    let template = index.template_content()?;
+    let config = template.tool_config()?;
+    assert_eq!(Some(Formality::PreferLess), config.formality);
+    assert_eq!(Some("Mobile banking app"), config.context.as_deref());
+    assert_eq!(vec!["brand".to_owned()], config.exclude);
+    assert!(template.is_excluded("brand"));
+    assert!(!template.is_excluded("title"));
+    Ok(())
+}
+
+#[test]
+pub fn directives_invalid() -> Result<()> {
+    let template: ArbFile = serde_json::from_str(
+        r#"{
+  "@@x-arb-formality": "polite"
+}"#,
+    )?;
+    assert!(matches!(
+        template.tool_config(),
+        Err(Error::InvalidDirective(_, _))
+    ));
+    Ok(())
+}
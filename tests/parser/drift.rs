@@ -0,0 +1,31 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl};
+
+#[test]
+pub fn drift() -> Result<()> {
+    let index = Intl::new("tests/fixtures/drift.yaml")?;
+    assert!(index.cache().get_file(&Lang::Fr).is_some());
+
+    let drift = index.drift(Lang::Fr)?;
+    assert_eq!(2, drift.len());
+
+    let edited = drift.iter().find(|x| x.key == "farewell").unwrap();
+    assert_eq!("Au revoir", edited.cached);
+    assert_eq!(Some("Salut"), edited.current.as_deref());
+
+    let removed = drift.iter().find(|x| x.key == "title").unwrap();
+    assert_eq!(None, removed.current);
+
+    assert!(index.drift(Lang::De)?.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn manual_edits() -> Result<()> {
+    let index = Intl::new("tests/fixtures/drift.yaml")?;
+    let edits = index.manual_edits(Lang::Fr)?;
+    assert_eq!(1, edits.len());
+    let value = edits.lookup("farewell").unwrap();
+    assert_eq!(Some("Salut"), value.value().as_str());
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use arb_lib::{protect_entities, restore_entities};
+
+#[test]
+pub fn entity_protect_restore() {
+    let source = "Terms&nbsp;&amp; conditions &#169; &#x2014; Tom & Jerry";
+    let (protected, entities) = protect_entities(source);
+    assert_eq!(
+        "Terms<ent>0</ent><ent>1</ent> conditions <ent>2</ent> <ent>3</ent> Tom & Jerry",
+        protected
+    );
+    assert_eq!(
+        vec![
+            "&nbsp;".to_string(),
+            "&amp;".to_string(),
+            "&#169;".to_string(),
+            "&#x2014;".to_string(),
+        ],
+        entities
+    );
+
+    let translated =
+        "Conditions<ent>0</ent><ent>1</ent> générales <ent>2</ent> <ent>3</ent> Tom & Jerry";
+    assert_eq!(
+        "Conditions&nbsp;&amp; générales &#169; &#x2014; Tom & Jerry",
+        restore_entities(translated, &entities)
+    );
+}
+
+#[test]
+pub fn entity_invalid() {
+    let source = "a & b; &#; &#xZ; &1a;";
+    let (protected, entities) = protect_entities(source);
+    assert_eq!(source, protected);
+    assert!(entities.is_empty());
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use arb_lib::deepl::{Formality, Lang, TranslateTextRequest};
+
+#[test]
+pub fn formality_serialize() -> Result<()> {
+    for (formality, expected) in [
+        (Formality::Default, "default"),
+        (Formality::More, "more"),
+        (Formality::Less, "less"),
+        (Formality::PreferMore, "prefer_more"),
+        (Formality::PreferLess, "prefer_less"),
+    ] {
+        assert_eq!(
+            serde_json::json!(expected),
+            serde_json::to_value(formality)?
+        );
+        assert_eq!(
+            formality,
+            serde_json::from_value(serde_json::json!(expected))?
+        );
+    }
+
+    let mut request = TranslateTextRequest::new(vec!["Hello".to_owned()], Lang::Fr);
+    request.formality = Some(Formality::PreferLess);
+    let value = serde_json::to_value(&request)?;
+    assert_eq!("prefer_less", value["formality"]);
+    Ok(())
+}
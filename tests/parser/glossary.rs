@@ -0,0 +1,46 @@
+use anyhow::Result;
+use arb_lib::deepl::{CreateGlossaryRequest, GlossaryEntriesFormat, Lang};
+
+#[test]
+pub fn glossary_entries_tsv() -> Result<()> {
+    let format = GlossaryEntriesFormat::from_path("glossary.tsv");
+    assert_eq!(GlossaryEntriesFormat::Tsv, format);
+    let entries = format.parse("Save\tEnregistrer\n\nVault\tCoffre\n")?;
+    assert_eq!(
+        vec![
+            ("Save".to_string(), "Enregistrer".to_string()),
+            ("Vault".to_string(), "Coffre".to_string()),
+        ],
+        entries
+    );
+
+    assert!(format.parse("Save\n").is_err());
+    assert!(format.parse("Save\t\n").is_err());
+    Ok(())
+}
+
+#[test]
+pub fn glossary_entries_csv() -> Result<()> {
+    let format = GlossaryEntriesFormat::from_path("glossary.CSV");
+    assert_eq!(GlossaryEntriesFormat::Csv, format);
+    let entries = format.parse("\"Save, now\",\"Enregistrer \"\"maintenant\"\"\"\n")?;
+    assert_eq!(
+        vec![(
+            "Save, now".to_string(),
+            "Enregistrer \"maintenant\"".to_string()
+        )],
+        entries
+    );
+
+    assert!(format.parse("\"Save,Enregistrer\n").is_err());
+    assert!(format.parse("Save,Enregistrer,Extra\n").is_err());
+    Ok(())
+}
+
+#[test]
+pub fn glossary_request_base_lang() {
+    let entries = vec![("Hello".to_owned(), "Olá".to_owned())];
+    let request = CreateGlossaryRequest::new("app", Lang::EnUs, Lang::PtBr, &entries);
+    assert_eq!(Lang::En, request.source_lang);
+    assert_eq!(Lang::Pt, request.target_lang);
+}
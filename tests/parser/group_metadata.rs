@@ -0,0 +1,35 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn group_metadata() -> Result<()> {
+    let mut file: ArbFile = serde_json::from_str(
+        r#"{
+  "@@locale": "fr",
+  "title": "Bienvenue",
+  "greeting": "Bonjour",
+  "@title": {},
+  "@orphan": {},
+  "@greeting": {}
+}"#,
+    )?;
+    file.group_metadata();
+
+    let keys: Vec<String> = file
+        .entries()
+        .iter()
+        .map(|entry| entry.key().to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "@@locale",
+            "title",
+            "@title",
+            "greeting",
+            "@greeting",
+            "@orphan"
+        ],
+        keys
+    );
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use arb_lib::{ArbValue, IcuKind, IcuMessage, IcuNode};
+use serde_json::Value;
+
+#[test]
+pub fn parse_icu_plural() -> Result<()> {
+    let value = Value::String(
+        "{count, plural, offset:1 =0{No items} one{# item for {name}} other{# items}}".to_string(),
+    );
+    let message = ArbValue::new(&value).parse_icu()?;
+    assert_eq!(1, message.nodes.len());
+    let IcuNode::Branches {
+        argument,
+        kind,
+        offset,
+        branches,
+    } = &message.nodes[0]
+    else {
+        panic!("expected branches");
+    };
+    assert_eq!("count", argument);
+    assert_eq!(IcuKind::Plural, *kind);
+    assert_eq!(Some(1), *offset);
+    let selectors = branches
+        .iter()
+        .map(|branch| branch.selector.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["=0", "one", "other"], selectors);
+    assert_eq!(IcuNode::Pound, branches[1].message.nodes[0]);
+    assert_eq!(vec!["count", "name"], message.arguments());
+    Ok(())
+}
+
+#[test]
+pub fn parse_icu_arguments() -> Result<()> {
+    let message = IcuMessage::parse(
+        "Total: {amount, number, currency} #{gender, select, female{her} other{their}}",
+    )?;
+    assert_eq!(IcuNode::Text("Total: ".to_string()), message.nodes[0]);
+    assert_eq!(
+        IcuNode::Argument {
+            name: "amount".to_string(),
+            format: Some("number".to_string()),
+            style: Some("currency".to_string()),
+        },
+        message.nodes[1]
+    );
+    // Number sign is only special in plural branches
+    assert_eq!(IcuNode::Text(" #".to_string()), message.nodes[2]);
+    assert_eq!(vec!["amount", "gender"], message.arguments());
+    Ok(())
+}
+
+#[test]
+pub fn parse_icu_errors() {
+    assert!(IcuMessage::parse("Hello {name").is_err());
+    assert!(IcuMessage::parse("Hello name}").is_err());
+    assert!(IcuMessage::parse("{count, plural, one{# item}}").is_err());
+    assert!(IcuMessage::parse("{count, plural, one{# item} other{# items}").is_err());
+}
+
+#[test]
+pub fn parse_icu_quoted() -> Result<()> {
+    let message = IcuMessage::parse("It''s '{literal}' for {name}")?;
+    assert_eq!(
+        IcuNode::Text("It's {literal} for ".to_string()),
+        message.nodes[0]
+    );
+    assert_eq!(vec!["name"], message.arguments());
+
+    // Apostrophes before other characters are literal
+    let message = IcuMessage::parse("Don't")?;
+    assert_eq!(vec![IcuNode::Text("Don't".to_string())], message.nodes);
+    Ok(())
+}
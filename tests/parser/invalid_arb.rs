@@ -0,0 +1,15 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Error, Intl};
+use std::path::PathBuf;
+
+#[test]
+pub fn invalid_arb_top_level() -> Result<()> {
+    let index = Intl::new("tests/fixtures/invalid_arb.yaml")?;
+    let result = index.load(Lang::Fr);
+    let Err(Error::InvalidArb(path, message)) = result else {
+        panic!("expected invalid arb error");
+    };
+    assert_eq!(PathBuf::from("tests/fixtures/invalid_arb/app_fr.arb"), path);
+    assert_eq!("expected a JSON object of key/value pairs", message);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use arb_lib::deepl::{Lang, Language, TargetLang};
+use std::collections::HashSet;
+
+#[test]
+pub fn lang_round_trip() -> Result<()> {
+    let all = Lang::all();
+    let unique = all.iter().collect::<HashSet<_>>();
+    assert_eq!(all.len(), unique.len());
+    // Listed in declaration order
+    assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+
+    for lang in all {
+        let code = lang.to_string();
+        assert_eq!(*lang, code.parse::<Lang>()?);
+        assert_eq!(*lang, code.to_uppercase().parse::<Lang>()?);
+        assert_eq!(*lang, code.to_lowercase().parse::<Lang>()?);
+    }
+    Ok(())
+}
+
+#[test]
+pub fn base_lang() {
+    assert_eq!(Lang::En, Lang::EnUs.base());
+    assert_eq!(Lang::En, Lang::EnGb.base());
+    assert_eq!(Lang::Pt, Lang::PtBr.base());
+    assert_eq!(Lang::Fr, Lang::Fr.base());
+}
+
+#[test]
+pub fn target_is_lang() {
+    assert!(TargetLang::from(Lang::En).is_lang(Lang::En));
+    assert!(TargetLang::Code("en".to_owned()).is_lang(Lang::En));
+    assert!(TargetLang::Code("EN".to_owned()).is_lang(Lang::En));
+    assert!(!TargetLang::Code("en-gb".to_owned()).is_lang(Lang::En));
+    assert!(!TargetLang::Code("zh-hant".to_owned()).is_lang(Lang::Zh));
+}
+
+#[test]
+pub fn language_codes() -> Result<()> {
+    // Codes not declared in Lang are still deserialized
+    let json = r#"[
+        {"language": "DE", "name": "German", "supports_formality": true},
+        {"language": "ES-419", "name": "Spanish (Latin American)"},
+        {"language": "ZH-HANS", "name": "Chinese (simplified)"}
+    ]"#;
+    let languages: Vec<Language> = serde_json::from_str(json)?;
+    assert_eq!(3, languages.len());
+    assert_eq!(Some(Lang::De), languages[0].lang());
+    assert_eq!("ES-419", languages[1].language);
+    assert_eq!(None, languages[2].lang());
+    Ok(())
+}
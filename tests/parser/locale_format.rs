@@ -0,0 +1,32 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, LocaleFormat};
+
+#[test]
+pub fn locale_format_hyphen() -> Result<()> {
+    let index = Intl::new("tests/fixtures/locale_format.yaml")?;
+    assert_eq!(LocaleFormat::Hyphen, index.locale_format());
+
+    let translated = index.list_translated()?;
+    assert!(translated.contains_key(&Lang::EnGb));
+    assert!(!translated.contains_key(&Lang::PtBr));
+    assert_eq!("app_en-gb.arb", index.format_file_name(Lang::EnGb));
+    assert!(index.normalize_file_names(true)?.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn locale_format_region() -> Result<()> {
+    let mut index = Intl::new("tests/fixtures/hyphenated.yaml")?;
+    assert_eq!("app_en_us.arb", index.format_file_name(Lang::EnUs));
+
+    index.set_locale_format(LocaleFormat::HyphenRegion);
+    assert_eq!("app_en-US.arb", index.format_file_name(Lang::EnUs));
+    assert_eq!("zh-Hans", LocaleFormat::HyphenRegion.format_code("ZH-HANS"));
+    assert_eq!(Some(Lang::EnUs), index.parse_file_name("app_en-US.arb"));
+    assert_eq!(None, index.parse_file_name("app_en_US.arb"));
+
+    index.set_locale_format(LocaleFormat::UnderscoreRegion);
+    assert_eq!("app_pt_BR.arb", index.format_file_name(Lang::PtBr));
+    assert_eq!(Some(Lang::PtBr), index.parse_file_name("app_pt_BR.arb"));
+    Ok(())
+}
@@ -1,3 +1,34 @@
+mod api_options;
+mod bom;
+mod cache;
+mod config_api_key;
+mod content_eq;
+mod description;
 mod diff;
+mod directives;
+mod drift;
+mod entity;
+mod formality;
+mod glossary;
+mod group_metadata;
+mod icu;
+mod invalid_arb;
+mod lang;
+mod locale_format;
+mod markdown;
+mod metadata_filter;
+mod normalize;
+mod output_directory;
 mod parse;
+mod patch;
+mod placeholder_names;
+mod placeholder_stats;
+mod plural;
+mod rename;
+mod retain;
+mod retry;
+mod review;
+mod source_hash;
+mod template_parts;
+mod to_template;
 mod translated_languages;
@@ -0,0 +1,29 @@
+use arb_lib::{protect_markdown, restore_markdown};
+
+#[test]
+pub fn markdown_protect_restore() {
+    let source = "See the [guide](https://example.com/guide) and run `arb help`.";
+    let (protected, segments) = protect_markdown(source);
+    assert_eq!("See the [guide]<md>0</md> and run <md>1</md>.", protected);
+    assert_eq!(
+        vec![
+            "(https://example.com/guide)".to_string(),
+            "`arb help`".to_string()
+        ],
+        segments
+    );
+
+    let translated = "Consultez le [guide]<md>0</md> et exécutez <md>1</md>.";
+    assert_eq!(
+        "Consultez le [guide](https://example.com/guide) et exécutez `arb help`.",
+        restore_markdown(translated, &segments)
+    );
+}
+
+#[test]
+pub fn markdown_code_fence() {
+    let source = "Example:\n```\nlet x = `y`;\n```";
+    let (protected, segments) = protect_markdown(source);
+    assert_eq!("Example:\n<md>0</md>", protected);
+    assert_eq!(source, restore_markdown(&protected, &segments));
+}
@@ -0,0 +1,17 @@
+use arb_lib::MetadataFilter;
+use serde_json::json;
+
+#[test]
+pub fn metadata_filter() {
+    let value = json!({
+        "description": "Greeting",
+        "x-jira": "APP-123",
+        "x-owner": "design"
+    });
+
+    let filter = MetadataFilter::Deny(vec!["x-jira".to_string(), "x-owner".to_string()]);
+    assert_eq!(json!({"description": "Greeting"}), filter.apply(&value));
+
+    let filter = MetadataFilter::Allow(vec!["x-owner".to_string()]);
+    assert_eq!(json!({"x-owner": "design"}), filter.apply(&value));
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Error, Intl};
+
+#[test]
+pub fn hyphenated_file_names() -> Result<()> {
+    let index = Intl::new("tests/fixtures/hyphenated.yaml")?;
+    let translated = index.list_translated()?;
+    assert!(translated.contains_key(&Lang::PtBr));
+    assert!(!translated.contains_key(&Lang::EnGb));
+
+    let renames = index.normalize_file_names(true)?;
+    assert_eq!(1, renames.len());
+    let (from, to) = &renames[0];
+    assert!(from.ends_with("app_en-gb.arb"));
+    assert!(to.ends_with("app_en_gb.arb"));
+    Ok(())
+}
+
+#[test]
+pub fn strict_file_names() -> Result<()> {
+    let mut index = Intl::new("tests/fixtures/hyphenated.yaml")?;
+    index.set_strict(true);
+    assert!(matches!(
+        index.list_translated(),
+        Err(Error::InvalidFileName(_))
+    ));
+    Ok(())
+}
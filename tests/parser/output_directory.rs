@@ -0,0 +1,24 @@
+use anyhow::Result;
+use arb_lib::{Error, Intl};
+
+#[test]
+pub fn ensure_output_directory() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("arb-output-{}", std::process::id()));
+    let mut index = Intl::new("tests/fixtures/basic.yaml")?;
+    index.set_output_dir(Some(dir.join("l10n")));
+    assert!(!dir.exists());
+
+    let created = index.ensure_output_directory()?;
+    assert_eq!(dir.join("l10n"), created);
+    assert!(created.is_dir());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+pub fn missing_arb_directory() -> Result<()> {
+    let result = Intl::new("tests/fixtures/missing_dir.yaml");
+    assert!(matches!(result, Err(Error::NotDirectory(_))));
+    Ok(())
+}
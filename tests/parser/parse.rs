@@ -1,5 +1,6 @@
 use anyhow::Result;
-use arb_lib::{ArbKey, Intl};
+use arb_lib::{deepl::Lang, ArbFile, ArbKey, Error, Intl};
+use std::path::PathBuf;
 
 #[test]
 pub fn parse_index_with_template() -> Result<()> {
@@ -19,9 +20,113 @@ pub fn parse_index_with_template() -> Result<()> {
     let value = template.lookup("nonExistent");
     assert!(value.is_none());
 
+    assert!(template.contains_key("helloWorld"));
+    assert!(!template.contains_key("nonExistent"));
+    assert_eq!(Some(1), template.index_of("helloWorld"));
+    assert_eq!(None, template.index_of("nonExistent"));
+
     let key_name = ArbKey::new("helloName");
     let placeholders = template.placeholders(&key_name)?;
     assert_eq!(placeholders.unwrap().to_vec(), vec!["name"]);
 
     Ok(())
 }
+
+#[test]
+pub fn glossary_entries() -> Result<()> {
+    let index = Intl::new("tests/fixtures/overrides.yaml")?;
+    let template = index.template_content()?;
+    let french = index.load(Lang::Fr)?;
+
+    let entries = template.glossary_entries(&french);
+    assert_eq!(vec![("Hello".to_string(), "Bonjour".to_string())], entries);
+
+    Ok(())
+}
+
+#[test]
+pub fn key_context() -> Result<()> {
+    let file: ArbFile = serde_json::from_str(
+        r#"{
+  "save": "Save",
+  "@save": {
+    "context": "Button to save a budget"
+  },
+  "cancel": "Cancel"
+}"#,
+    )?;
+    assert_eq!(Some("Button to save a budget"), file.context("save"));
+    assert_eq!(None, file.context("cancel"));
+    Ok(())
+}
+
+#[test]
+pub fn all_placeholders() -> Result<()> {
+    let file: ArbFile = serde_json::from_str(
+        r#"{
+  "helloName": "Hello {name}",
+  "@helloName": {
+    "placeholders": {
+      "name": {}
+    }
+  },
+  "items": "{count, plural, one{1 item} other{{count} items}}",
+  "plain": "Hello world"
+}"#,
+    )?;
+    let placeholders = file.all_placeholders();
+    assert_eq!(3, placeholders.len());
+    assert_eq!(vec!["name"], placeholders["helloName"]);
+    assert_eq!(vec!["count"], placeholders["items"]);
+    assert!(placeholders["plain"].is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn parse_index_template_language() -> Result<()> {
+    let index = Intl::new("tests/fixtures/template_language.yaml")?;
+    assert_eq!(&Lang::En, index.template_language());
+    assert!(index.template_content()?.contains_key("message"));
+
+    let result = Intl::new("tests/fixtures/invalid_template_language.yaml");
+    assert!(matches!(
+        result,
+        Err(arb_lib::Error::InvalidTemplateLanguage(_, _))
+    ));
+
+    Ok(())
+}
+
+#[test]
+pub fn translatable_entries() -> Result<()> {
+    let file: ArbFile = serde_json::from_str(
+        r#"{
+  "@@locale": "en",
+  "greeting": "Hello",
+  "@greeting": {
+    "description": "Greeting"
+  },
+  "count": 1
+}"#,
+    )?;
+    let keys = file
+        .translatable_entries()
+        .iter()
+        .map(|entry| entry.key().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["greeting"], keys);
+    Ok(())
+}
+
+#[test]
+pub fn template_not_found() -> Result<()> {
+    let index = Intl::new("tests/fixtures/missing_template.yaml")?;
+    let Err(Error::TemplateNotFound(path)) = index.template_content() else {
+        panic!("expected template not found error");
+    };
+    assert_eq!(
+        PathBuf::from("tests/fixtures/missing_template/app_en.arb"),
+        path
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use arb_lib::unified_diff;
+
+#[test]
+pub fn patch_update() -> Result<()> {
+    let old =
+        "{\n  \"a\": \"A\",\n  \"b\": \"B\",\n  \"c\": \"C\",\n  \"d\": \"D\",\n  \"e\": \"E\"\n}";
+    let new =
+        "{\n  \"a\": \"A\",\n  \"b\": \"B\",\n  \"c\": \"X\",\n  \"d\": \"D\",\n  \"e\": \"E\"\n}";
+    let patch = unified_diff("app_fr.arb", old, new);
+    let expected = r#"diff --git a/app_fr.arb b/app_fr.arb
+--- a/app_fr.arb
++++ b/app_fr.arb
+@@ -1,7 +1,7 @@
+ {
+   "a": "A",
+   "b": "B",
+-  "c": "C",
++  "c": "X",
+   "d": "D",
+   "e": "E"
+ }
+"#;
+    assert_eq!(expected, patch);
+    assert!(unified_diff("app_fr.arb", old, old).is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn patch_create() -> Result<()> {
+    let patch = unified_diff("app_fr.arb", "", "{\n  \"a\": \"A\"\n}");
+    let expected = r#"diff --git a/app_fr.arb b/app_fr.arb
+--- /dev/null
++++ b/app_fr.arb
+@@ -0,0 +1,3 @@
++{
++  "a": "A"
++}
+"#;
+    assert_eq!(expected, patch);
+    Ok(())
+}
+
+#[test]
+pub fn patch_hunks() -> Result<()> {
+    let old = (1..=20).map(|n| n.to_string()).collect::<Vec<_>>();
+    let mut new = old.clone();
+    new[1] = "two".to_owned();
+    new.remove(15);
+    let patch = unified_diff("lines", &old.join("\n"), &new.join("\n"));
+    let headers = patch
+        .lines()
+        .filter(|line| line.starts_with("@@"))
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["@@ -1,5 +1,5 @@", "@@ -13,7 +13,6 @@"], headers);
+    Ok(())
+}
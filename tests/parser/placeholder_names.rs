@@ -0,0 +1,45 @@
+use anyhow::Result;
+use arb_lib::ArbValue;
+use serde_json::Value;
+
+fn names(source: &str) -> Vec<String> {
+    let value = Value::String(source.to_owned());
+    ArbValue::new(&value).placeholder_names()
+}
+
+#[test]
+pub fn placeholder_names() -> Result<()> {
+    assert_eq!(vec!["name"], names("Hello {name}"));
+    assert_eq!(vec!["first", "last"], names("{first} {last}"));
+    assert!(names("No placeholders").is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn placeholder_names_repeated() -> Result<()> {
+    assert_eq!(
+        vec!["name", "count"],
+        names("{name}, {name} has {count} items")
+    );
+    assert_eq!(
+        vec!["count"],
+        names("{count, plural, =1{{count} item} other{{count} items}}")
+    );
+    Ok(())
+}
+
+#[test]
+pub fn placeholder_names_escaped() -> Result<()> {
+    assert!(names("Use '{name}' in templates").is_empty());
+    assert_eq!(vec!["name"], names("It''s {name}"));
+    assert_eq!(vec!["name"], names("'{literal}' and {name}"));
+    assert_eq!(vec!["name"], names("Don't {name}"));
+    Ok(())
+}
+
+#[test]
+pub fn placeholder_names_not_string() -> Result<()> {
+    let value = serde_json::json!({ "description": "{name}" });
+    assert!(ArbValue::new(&value).placeholder_names().is_empty());
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn placeholder_stats() -> Result<()> {
+    let template: ArbFile = serde_json::from_str(
+        r#"{
+  "@@locale": "en",
+  "greeting": "Hello {name}",
+  "summary": "{name} has {count} items in {folder}",
+  "@summary": {
+    "placeholders": {
+      "name": {},
+      "count": {},
+      "folder": {}
+    }
+  },
+  "title": "Welcome"
+}"#,
+    )?;
+
+    let stats = template.placeholder_stats();
+    assert_eq!(3, stats.total_keys);
+    assert_eq!(2, stats.keys.len());
+    assert_eq!(
+        Some(&vec![
+            "name".to_owned(),
+            "count".to_owned(),
+            "folder".to_owned()
+        ]),
+        stats.keys.get("summary")
+    );
+    assert_eq!(Some(&2), stats.names.get("name"));
+    assert_eq!(Some(&1), stats.names.get("count"));
+    assert_eq!(3, stats.max);
+    assert!((stats.mean - 4.0 / 3.0).abs() < f64::EPSILON);
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use arb_lib::{Error, Placeholders};
+
+#[test]
+pub fn plural_branches() -> Result<()> {
+    let placeholders = Placeholders::new(vec!["count"]);
+    let source = "{count, plural, =0{No items} one{{count} item} other{{count} items}}";
+    placeholders.verify(source)?;
+    placeholders.verify("{count, plural, one{# item} other{# items}}")?;
+
+    // Number sign is a use of the plural argument
+    placeholders.verify_translation(
+        source,
+        "{count, plural, =0{Aucun} one{# article} other{# articles}}",
+    )?;
+
+    let result = placeholders.verify_translation(
+        source,
+        "{count, plural, =0{Aucun} one{{count} article} other{Des articles}}",
+    );
+    assert!(matches!(
+        result,
+        Err(Error::PlaceholderMissingInBranch(name, branch, _)) if name == "count" && branch == "other"
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn select_branches() -> Result<()> {
+    let placeholders = Placeholders::new(vec!["gender", "name"]);
+    let source = "{gender, select, male{He met {name}} other{They met {name}}}";
+    placeholders.verify(source)?;
+    placeholders.verify_translation(
+        source,
+        "{gender, select, male{Il a vu {name}} other{Ils ont vu {name}}}",
+    )?;
+
+    let result = placeholders.verify_translation(
+        source,
+        "{gender, select, male{Il a vu {name}} other{Ils ont vu}}",
+    );
+    assert!(matches!(
+        result,
+        Err(Error::PlaceholderMissingInBranch(name, branch, _)) if name == "name" && branch == "other"
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn optional_branches() -> Result<()> {
+    // Names may be omitted from some branches of the source
+    let placeholders = Placeholders::new(vec!["n", "sender"]);
+    let source = "{n, plural, =0{Nothing from {sender}} other{# messages}}";
+    placeholders.verify(source)?;
+    placeholders.verify_translation(
+        source,
+        "{n, plural, =0{Rien de {sender}} other{# messages}}",
+    )?;
+
+    // Selectors missing from the translation are ignored
+    placeholders.verify_translation(source, "{n, plural, other{# messages}}")?;
+    Ok(())
+}
+
+#[test]
+pub fn nested_branches() -> Result<()> {
+    let placeholders = Placeholders::new(vec!["gender", "count", "name"]);
+    let source =
+        "{gender, select, male{{count, plural, =1{{name} has one} other{{name} has #}}} other{{name} has {count}}}";
+    placeholders.verify(source)?;
+    let result = placeholders.verify_translation(
+        source,
+        "{gender, select, male{{count, plural, =1{Il en a un} other{{name} en a #}}} other{{name} en a {count}}}",
+    );
+    assert!(matches!(
+        result,
+        Err(Error::PlaceholderMissingInBranch(name, branch, _)) if name == "name" && branch == "=1"
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn unparseable_source() -> Result<()> {
+    // Not valid ICU so there is no branch information
+    let placeholders = Placeholders::new(vec!["name"]);
+    placeholders.verify("{0} and {name}")?;
+    placeholders.verify_translation("{0} and {name}", "{0} et")?;
+
+    let result = placeholders.verify("{0} and someone");
+    assert!(matches!(result, Err(Error::PlaceholderNotDefined(_, _))));
+    Ok(())
+}
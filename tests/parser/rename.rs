@@ -0,0 +1,29 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn rename_key() -> Result<()> {
+    let mut file: ArbFile = serde_json::from_str(
+        r#"{
+  "first": "First",
+  "greeting": "Hello",
+  "@greeting": {
+    "description": "Greeting"
+  },
+  "last": "Last"
+}"#,
+    )?;
+
+    assert!(file.rename_key("greeting", "welcome"));
+    let keys = file
+        .entries()
+        .iter()
+        .map(|entry| entry.key().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["first", "welcome", "@welcome", "last"], keys);
+    assert_eq!(Some("Greeting"), file.description("welcome"));
+    assert_eq!(None, file.description("greeting"));
+
+    assert!(!file.rename_key("missing", "other"));
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use arb_lib::{ArbFile, ArbKey, Intl};
+
+#[test]
+pub fn retain_keeps_metadata() -> Result<()> {
+    let index = Intl::new("tests/fixtures/basic.yaml")?;
+    let mut template = index.template_content()?;
+
+    template.retain(|key, _| key.as_ref() == "helloName");
+
+    assert!(template.lookup("helloName").is_some());
+    assert!(template.lookup("@helloName").is_some());
+    assert!(template.lookup("@@locale").is_some());
+    assert!(template.lookup("helloWorld").is_none());
+    assert!(template.lookup("@_END").is_none());
+
+    Ok(())
+}
+
+#[test]
+pub fn metadata_keys() {
+    let key = ArbKey::new("@@locale");
+    assert!(key.is_global_metadata());
+    assert!(!key.is_metadata());
+    assert_eq!(None, key.data_key());
+
+    let key = ArbKey::new("@message");
+    assert!(!key.is_global_metadata());
+    assert!(key.is_metadata());
+    assert_eq!(Some("message"), key.data_key());
+
+    assert!(ArbFile::is_metadata_key("@message"));
+    assert!(!ArbFile::is_metadata_key("message"));
+}
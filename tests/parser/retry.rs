@@ -0,0 +1,81 @@
+use anyhow::Result;
+use arb_lib::deepl::{ApiOptions, DeeplApi, Error};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+const USAGE: &str = r#"{"character_count":1,"character_limit":10}"#;
+
+/// Start a server that responds with each status in turn and
+/// then with usage; returns the endpoint and request counter.
+async fn mock_server(statuses: Vec<u16>) -> Result<(String, Arc<AtomicUsize>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}/", listener.local_addr()?);
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let index = counter.fetch_add(1, Ordering::SeqCst);
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buffer).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let (status, body) = match statuses.get(index) {
+                Some(status) => (*status, r#"{"message":"mock error"}"#),
+                None => (200, USAGE),
+            };
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    Ok((endpoint, requests))
+}
+
+fn new_api(endpoint: &str, max_retries: u32) -> DeeplApi {
+    DeeplApi::new(
+        ApiOptions::new("not-used")
+            .with_endpoint(endpoint.parse().unwrap())
+            .max_retries(max_retries),
+    )
+}
+
+#[tokio::test]
+pub async fn retry_rate_limited() -> Result<()> {
+    let (endpoint, requests) = mock_server(vec![429, 503]).await?;
+    let usage = new_api(&endpoint, 2).usage().await?;
+    assert_eq!(10, usage.character_limit);
+    assert_eq!(3, requests.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn retry_exhausted() -> Result<()> {
+    let (endpoint, requests) = mock_server(vec![500, 500]).await?;
+    let result = new_api(&endpoint, 1).usage().await;
+    assert!(matches!(result, Err(Error::Api(500, _))));
+    assert_eq!(2, requests.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn no_retry_client_error() -> Result<()> {
+    let (endpoint, requests) = mock_server(vec![403]).await?;
+    let result = new_api(&endpoint, 2).usage().await;
+    assert!(matches!(result, Err(Error::Api(403, message)) if message == "mock error"));
+    assert_eq!(1, requests.load(Ordering::SeqCst));
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl};
+
+#[test]
+pub fn export_review() -> Result<()> {
+    let index = Intl::new("tests/fixtures/review.yaml")?;
+    let review = index.export_review(Lang::Fr)?;
+    assert_eq!(2, review.len());
+
+    let greeting = review.get("greeting").unwrap();
+    assert_eq!("Hello", greeting.source);
+    assert_eq!(Some("Bonjour"), greeting.target.as_deref());
+    assert_eq!(
+        Some("Greeting shown on the home screen"),
+        greeting.description.as_deref()
+    );
+
+    let farewell = review.get("farewell").unwrap();
+    assert!(farewell.target.is_none());
+    assert!(farewell.description.is_none());
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use arb_lib::{hash_source, ArbFile};
+
+#[test]
+pub fn source_hash() -> Result<()> {
+    assert_eq!("63f0bfacf2c00f6b", hash_source("Hello"));
+
+    let mut file: ArbFile = serde_json::from_str(
+        r#"{
+  "greeting": "Bonjour"
+}"#,
+    )?;
+    assert_eq!(None, file.source_hash("greeting"));
+    file.set_source_hash("greeting", hash_source("Hello"));
+    assert_eq!(Some("63f0bfacf2c00f6b"), file.source_hash("greeting"));
+    assert_eq!(Some(1), file.index_of("@greeting"));
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl};
+
+#[test]
+pub fn template_parts() -> Result<()> {
+    let index = Intl::new("tests/fixtures/template_parts.yaml")?;
+    assert_eq!(&["app_en_settings.arb".to_string()], index.template_parts());
+
+    let template = index.template_content()?;
+    assert!(template.contains_key("title"));
+    assert!(template.contains_key("theme"));
+    assert!(template.contains_key("@theme"));
+
+    let translated = index.list_translated()?;
+    assert_eq!(2, translated.len());
+
+    let file = index.load(Lang::Fr)?;
+    assert_eq!(2, file.len());
+
+    let mut file = file;
+    file.set_source_hash("theme", "0");
+    let files = index.split_translation("fr", &file)?;
+    assert_eq!(2, files.len());
+    assert!(files[0].0.ends_with("app_fr.arb"));
+    assert!(files[0].1.contains_key("title"));
+    assert!(files[1].0.ends_with("app_fr_settings.arb"));
+    assert!(files[1].1.contains_key("theme"));
+    assert!(files[1].1.contains_key("@theme"));
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use arb_lib::ArbFile;
+
+#[test]
+pub fn to_template() -> Result<()> {
+    let file: ArbFile = serde_json::from_str(
+        r#"{
+  "@@locale": "fr",
+  "greeting": "Bonjour {name}",
+  "@greeting": {
+    "description": "Greeting",
+    "placeholders": {
+      "name": {}
+    }
+  },
+  "farewell": "Au revoir"
+}"#,
+    )?;
+
+    let template = file.to_template();
+    let value = serde_json::to_value(&template)?;
+    assert_eq!(
+        serde_json::json!({
+            "greeting": "",
+            "@greeting": {
+                "description": "Greeting",
+                "placeholders": {
+                    "name": {}
+                }
+            },
+            "farewell": ""
+        }),
+        value
+    );
+    assert_eq!(Some("Greeting"), template.description("greeting"));
+    Ok(())
+}
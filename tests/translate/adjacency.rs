@@ -0,0 +1,35 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn adjacency() -> Result<()> {
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
+    let index = "tests/fixtures/adjacency.yaml";
+    let intl = Intl::new(index)?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(1, result.length);
+
+    let keys: Vec<String> = result
+        .translated
+        .entries()
+        .iter()
+        .map(|entry| entry.key().to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "title",
+            "@title",
+            "greeting",
+            "@greeting",
+            "farewell",
+            "@farewell"
+        ],
+        keys
+    );
+    Ok(())
+}
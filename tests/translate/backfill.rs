@@ -0,0 +1,29 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl,
+};
+
+#[tokio::test]
+pub async fn backfill_dry_run() -> Result<()> {
+    // Dry runs make no requests so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let intl = Intl::new("tests/fixtures/backfill.yaml")?;
+    let proposed = intl.backfill(&api, Lang::Fr, true).await?;
+    assert_eq!(2, proposed.len());
+    assert!(!proposed.contains_key("title"));
+    let value = proposed.lookup("greeting").unwrap();
+    assert_eq!(Some("Bonjour {name}"), value.value().as_str());
+    assert!(proposed.contains_key("@greeting"));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn backfill() -> Result<()> {
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
+    let intl = Intl::new("tests/fixtures/backfill.yaml")?;
+    let proposed = intl.backfill(&api, Lang::Fr, false).await?;
+    let value = proposed.lookup("greeting").unwrap();
+    assert!(value.value().as_str().unwrap().contains("{name}"));
+    Ok(())
+}
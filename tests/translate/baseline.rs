@@ -0,0 +1,25 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, ArbFile, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn baseline() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/idempotent.yaml")?;
+
+    // Source changed since the baseline revision
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.baseline = Some(serde_json::from_str::<ArbFile>(r#"{"message": "Hi"}"#)?);
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+    assert!(result.diff.update.contains("message"));
+    let value = result.translated.lookup("message").unwrap();
+    assert_eq!(Some("[fr] Hello"), value.value().as_str());
+
+    // Unchanged since the baseline revision
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.baseline = Some(serde_json::from_str::<ArbFile>(r#"{"message": "Hello"}"#)?);
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+    assert_eq!(0, result.length);
+    Ok(())
+}
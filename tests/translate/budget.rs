@@ -0,0 +1,34 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, SkipReason, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn character_budget() -> Result<()> {
+    // Dry runs make no requests so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let intl = Intl::new("tests/fixtures/budget.yaml")?;
+    assert_eq!(Some(15), intl.character_budget(&Lang::Fr));
+    assert_eq!(None, intl.character_budget(&Lang::De));
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.dry_run = true;
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(vec!["helloName".to_string()], result.deferred);
+    assert_eq!(SkipReason::TooLong, result.skipped[0].reason);
+    assert_eq!(
+        serde_json::json!("too-long"),
+        serde_json::to_value(result.skipped[0].reason)?
+    );
+
+    // Options take precedence over the index file
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.dry_run = true;
+    options.disable_cache = true;
+    options.character_budget = Some(0);
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(2, result.deferred.len());
+    Ok(())
+}
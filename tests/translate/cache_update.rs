@@ -7,7 +7,7 @@ use serde_json::Value;
 
 #[tokio::test]
 pub async fn diff_cache() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
 
     let index = "tests/fixtures/diff_update.yaml";
     let options = TranslationOptions {
@@ -15,9 +15,26 @@ pub async fn diff_cache() -> Result<()> {
         dry_run: false,
         invalidation: Some(Invalidation::All),
         overrides: None,
+        post_process: None,
+        glossary_id: None,
+        global_context: None,
+        markup: Default::default(),
+        collect_errors: false,
+        target_code: None,
+        formality: None,
+        source_hash: false,
+        character_budget: None,
+        metadata_filter: None,
+        keep_on_empty: true,
+        retry_preserve_formatting: true,
+        output_order: Default::default(),
+        baseline: None,
+        placeholder_strategy: Default::default(),
+        ignore_whitespace: false,
+        existing: None,
         disable_cache: true,
     };
-    let mut intl = Intl::new(index)?;
+    let intl = Intl::new(index)?;
     let result = intl.translate(&api, options).await?;
     assert_eq!(1, result.length);
 
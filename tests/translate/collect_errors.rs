@@ -0,0 +1,27 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Error, Intl, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn collect_errors() -> Result<()> {
+    // No API calls are made so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/placeholder_error.yaml";
+
+    let intl = Intl::new(index)?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await;
+    assert!(matches!(result, Err(Error::PlaceholderNotDefined(_, _))));
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.collect_errors = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(0, result.length);
+    assert_eq!(1, result.errors.len());
+    assert_eq!("message", result.errors[0].0);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, TranslationOptions,
+};
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[tokio::test]
+pub async fn concurrent() -> Result<()> {
+    // No API calls are made so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/idempotent.yaml";
+    let intl = Intl::new(index)?;
+
+    let (en, fr) = tokio::join!(
+        assert_send(intl.translate(&api, TranslationOptions::new(Lang::En))),
+        assert_send(intl.translate(&api, TranslationOptions::new(Lang::Fr))),
+    );
+    assert_eq!(0, en?.length);
+    assert_eq!(0, fr?.length);
+    assert!(intl.cache().get_file(&Lang::Fr).is_some());
+
+    Ok(())
+}
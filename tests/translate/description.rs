@@ -0,0 +1,48 @@
+use crate::mock::Contextual;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn description_added_tracked() -> Result<()> {
+    let cache_path = "tests/fixtures/description_tracked/.cache.json";
+    let cache = std::fs::read(cache_path)?;
+
+    let mut intl = Intl::new("tests/fixtures/description_tracked.yaml")?;
+    intl.set_defer_cache(true);
+    let options = TranslationOptions::new(Lang::Fr);
+    let result = intl.translate(&Contextual, options).await?;
+    assert_eq!(1, result.length);
+    assert_eq!(1, result.diff.update.len());
+    assert!(result.diff.update.contains("save"));
+
+    let value = result.translated.lookup("save").unwrap();
+    assert_eq!(
+        Some("[Button to save a document] Save"),
+        value.value().as_str()
+    );
+    let value = result.translated.lookup("open").unwrap();
+    assert_eq!(Some("Ouvrir"), value.value().as_str());
+
+    let description = intl
+        .cache()
+        .get_file(&Lang::Fr)
+        .and_then(|file| file.description("save").map(|s| s.to_owned()));
+    assert_eq!(Some("Button to save a document"), description.as_deref());
+    assert_eq!(cache, std::fs::read(cache_path)?);
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn description_added_untracked() -> Result<()> {
+    let cache_path = "tests/fixtures/description_untracked/.cache.json";
+    let cache = std::fs::read(cache_path)?;
+
+    let intl = Intl::new("tests/fixtures/description_untracked.yaml")?;
+    let options = TranslationOptions::new(Lang::Fr);
+    let result = intl.translate(&Contextual, options).await?;
+    assert_eq!(0, result.length);
+    assert!(result.diff.update.is_empty());
+    assert!(!result.changed);
+    assert_eq!(cache, std::fs::read(cache_path)?);
+    Ok(())
+}
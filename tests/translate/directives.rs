@@ -0,0 +1,45 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Formality, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    Intl, TranslationOptions, TranslationProvider,
+};
+
+/// Provider that requires the formality and context
+/// declared in the template.
+struct Informal;
+
+impl TranslationProvider for Informal {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        assert_eq!(Some(Formality::PreferLess), request.formality);
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!("prefer_less", value["formality"]);
+        assert_eq!(Some("Mobile banking app"), request.context.as_deref());
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("[fr] {}", text),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn directives() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/directives.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Informal, options).await?;
+
+    let value = result.translated.lookup("brand").unwrap();
+    assert_eq!(Some("Save Our Secrets"), value.value().as_str());
+    let value = result.translated.lookup("title").unwrap();
+    assert_eq!(Some("[fr] Welcome"), value.value().as_str());
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, Provenance, SkipReason, TranslationOptions};
+
+#[tokio::test]
+pub async fn excluded() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/excluded.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+    assert_eq!(1, result.length);
+
+    let value = result.translated.lookup("brand").unwrap();
+    assert_eq!(Some("Save Our Secrets"), value.value().as_str());
+    let value = result.translated.lookup("title").unwrap();
+    assert_eq!(Some("[fr] Welcome"), value.value().as_str());
+    assert_eq!(SkipReason::Excluded, result.skipped[0].reason);
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn excluded_provenance() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/excluded.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+
+    let brand = result.provenance.get("brand").unwrap();
+    assert_eq!(Provenance::Excluded, brand.provenance);
+    assert_eq!(None, brand.detected_source);
+
+    let title = result.provenance.get("title").unwrap();
+    assert_eq!(Provenance::Machine, title.provenance);
+    assert_eq!(Some(Lang::En), title.detected_source);
+    assert_eq!("[fr] Welcome".chars().count(), title.characters);
+    Ok(())
+}
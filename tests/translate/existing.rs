@@ -0,0 +1,18 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, ArbFile, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn existing_output() -> Result<()> {
+    // Language file is up to date but the output file is empty
+    let intl = Intl::new("tests/fixtures/idempotent.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.existing = Some(ArbFile::default());
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+    assert!(result.changed);
+    assert!(result.diff.create.contains("message"));
+    let value = result.translated.lookup("message").unwrap();
+    assert_eq!(Some("[fr] Hello"), value.value().as_str());
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    FallbackProvider, Intl, TranslationOptions, TranslationProvider,
+};
+
+struct Unavailable;
+
+impl TranslationProvider for Unavailable {
+    async fn translate_text(
+        &self,
+        _request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Err(deepl::Error::Api(456, "Quota exceeded".to_string()))
+    }
+}
+
+struct Echo;
+
+impl TranslationProvider for Echo {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: text.to_uppercase(),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn fallback_provider() -> Result<()> {
+    let provider = FallbackProvider::new(Unavailable, Echo);
+    let intl = Intl::new("tests/fixtures/basic.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&provider, options).await?;
+    assert_eq!(2, result.length);
+    let value = result.translated.lookup("helloWorld").unwrap();
+    assert_eq!(Some("HELLO WORLD"), value.value().as_str());
+
+    // Errors from the fallback provider are returned
+    let provider = FallbackProvider::new(Unavailable, Unavailable);
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    assert!(intl.translate(&provider, options).await.is_err());
+    Ok(())
+}
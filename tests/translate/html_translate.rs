@@ -7,9 +7,9 @@ use serde_json::Value;
 
 #[tokio::test]
 pub async fn html_translate() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
     let index = "tests/fixtures/html.yaml";
-    let mut intl = Intl::new(index)?;
+    let intl = Intl::new(index)?;
     let options = TranslationOptions::new(Lang::Fr);
     let result = intl.translate(&api, options).await?;
 
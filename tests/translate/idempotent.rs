@@ -0,0 +1,26 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn idempotent() -> Result<()> {
+    // No API calls are made so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/idempotent.yaml";
+    let cache_path = "tests/fixtures/idempotent/.cache.json";
+    let cache = std::fs::read(cache_path)?;
+
+    for _ in 0..2 {
+        let intl = Intl::new(index)?;
+        let options = TranslationOptions::new(Lang::Fr);
+        let result = intl.translate(&api, options).await?;
+        assert_eq!(0, result.length);
+        assert!(!result.changed);
+        assert!(!result.cache_changed);
+        assert_eq!(cache, std::fs::read(cache_path)?);
+    }
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use arb_lib::{
 
 #[tokio::test]
 pub async fn invalidate_all() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
 
     let index = "tests/fixtures/invalidate.yaml";
     let options = TranslationOptions {
@@ -14,9 +14,26 @@ pub async fn invalidate_all() -> Result<()> {
         dry_run: false,
         invalidation: Some(Invalidation::All),
         overrides: None,
+        post_process: None,
+        glossary_id: None,
+        global_context: None,
+        markup: Default::default(),
+        collect_errors: false,
+        target_code: None,
+        formality: None,
+        source_hash: false,
+        character_budget: None,
+        metadata_filter: None,
+        keep_on_empty: true,
+        retry_preserve_formatting: true,
+        output_order: Default::default(),
+        baseline: None,
+        placeholder_strategy: Default::default(),
+        ignore_whitespace: false,
+        existing: None,
         disable_cache: false,
     };
-    let mut intl = Intl::new(index)?;
+    let intl = Intl::new(index)?;
     let result = intl.translate(&api, options).await?;
     assert_eq!(1, result.length);
     Ok(())
@@ -24,7 +41,7 @@ pub async fn invalidate_all() -> Result<()> {
 
 #[tokio::test]
 pub async fn invalidate_keys() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
 
     let index = "tests/fixtures/invalidate.yaml";
     let options = TranslationOptions {
@@ -32,9 +49,26 @@ pub async fn invalidate_keys() -> Result<()> {
         dry_run: false,
         invalidation: Some(Invalidation::Keys(vec!["message".to_owned()])),
         overrides: None,
+        post_process: None,
+        glossary_id: None,
+        global_context: None,
+        markup: Default::default(),
+        collect_errors: false,
+        target_code: None,
+        formality: None,
+        source_hash: false,
+        character_budget: None,
+        metadata_filter: None,
+        keep_on_empty: true,
+        retry_preserve_formatting: true,
+        output_order: Default::default(),
+        baseline: None,
+        placeholder_strategy: Default::default(),
+        ignore_whitespace: false,
+        existing: None,
         disable_cache: false,
     };
-    let mut intl = Intl::new(index)?;
+    let intl = Intl::new(index)?;
     let result = intl.translate(&api, options).await?;
     assert_eq!(1, result.length);
     Ok(())
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    Intl, Invalidation, Provenance, SkipReason, TranslationOptions, TranslationProvider, Warning,
+};
+
+/// Provider that returns empty translations.
+struct Empty;
+
+impl TranslationProvider for Empty {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|_| TextTranslation {
+                    text: String::new(),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn keep_on_empty() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/idempotent.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.invalidation = Some(Invalidation::All);
+    options.disable_cache = true;
+    let result = intl.translate(&Empty, options).await?;
+
+    let value = result.translated.lookup("message").unwrap();
+    assert_eq!(Some("Bonjour"), value.value().as_str());
+    assert!(matches!(
+        result.warnings.as_slice(),
+        [Warning::EmptyTranslation { key }] if key == "message"
+    ));
+    assert!(result
+        .skipped
+        .iter()
+        .any(|skipped| skipped.key == "message" && skipped.reason == SkipReason::Empty));
+    let provenance = result.provenance.get("message").unwrap();
+    assert_eq!(Provenance::Cached, provenance.provenance);
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.invalidation = Some(Invalidation::All);
+    options.keep_on_empty = false;
+    options.disable_cache = true;
+    let result = intl.translate(&Empty, options).await?;
+    let value = result.translated.lookup("message").unwrap();
+    assert_eq!(Some(""), value.value().as_str());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn keep_on_empty_retry() -> Result<()> {
+    let mut intl = Intl::new("tests/fixtures/diff_update.yaml")?;
+    intl.set_defer_cache(true);
+    for _ in 0..2 {
+        let options = TranslationOptions::new(Lang::Fr);
+        let result = intl.translate(&Empty, options).await?;
+        assert!(result.diff.update.contains("message"));
+        let value = result.translated.lookup("message").unwrap();
+        assert_eq!(Some("Bonjour"), value.value().as_str());
+
+        // Cache keeps the previous source so the key is retried
+        let cache = intl.cache();
+        let value = cache.get_file(&Lang::Fr).unwrap().lookup("message");
+        assert_eq!(Some("Hello"), value.unwrap().value().as_str());
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, Invalidation, SkipReason, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn locked() -> Result<()> {
+    // Locked keys are never sent so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/locked.yaml";
+    let intl = Intl::new(index)?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.invalidation = Some(Invalidation::All);
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(0, result.length);
+    assert!(!result.changed);
+    assert_eq!(1, result.skipped.len());
+    assert_eq!("terms", result.skipped[0].key);
+    assert_eq!(SkipReason::Locked, result.skipped[0].reason);
+    let value = result.translated.lookup("terms").unwrap();
+    assert_eq!(Some("Conditions d'utilisation"), value.value().as_str());
+    Ok(())
+}
@@ -1,7 +1,36 @@
+mod adjacency;
+mod backfill;
+mod baseline;
 mod basic_translate;
+mod budget;
 mod cache_update;
+mod collect_errors;
+mod concurrent;
+mod description;
+mod directives;
+mod excluded;
+mod existing;
+mod fallback;
 mod html_translate;
+mod idempotent;
 mod invalidate;
+mod keep_on_empty;
 mod languages;
+mod locked;
+mod metadata_filter;
+mod mock;
+mod namespace_context;
+mod output_directory;
+mod output_order;
 mod overrides;
+mod placeholder_retry;
+mod placeholder_strategy;
+mod plural_branches;
+mod post_process;
+mod template_language;
+mod translate_key;
+mod translate_keyed;
+mod translate_strings;
 mod usage;
+mod warnings;
+mod whitespace;
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, MetadataFilter, TranslationOptions,
+};
+use serde_json::json;
+
+#[tokio::test]
+pub async fn metadata_filter_existing() -> Result<()> {
+    // No API calls are made so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let intl = Intl::new("tests/fixtures/metadata_filter.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.metadata_filter = Some(MetadataFilter::Deny(vec!["x-jira".to_string()]));
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(0, result.length);
+    assert!(result.changed);
+    let translated = serde_json::to_value(&result.translated)?;
+    assert_eq!(Some("Bonjour"), translated["message"].as_str());
+    assert_eq!(json!({"description": "Greeting"}), translated["@message"]);
+    Ok(())
+}
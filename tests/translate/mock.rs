@@ -0,0 +1,73 @@
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    TranslationProvider,
+};
+use std::sync::Mutex;
+
+/// Provider that marks translated text.
+pub struct Marked;
+
+impl TranslationProvider for Marked {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("[fr] {}", text),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Provider that marks translated text with the context.
+pub struct Contextual;
+
+impl TranslationProvider for Contextual {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        let context = request.context.as_deref().unwrap_or_default();
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("[{}] {}", context, text),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Provider that marks translated text and records requests.
+#[derive(Default)]
+pub struct Recorder {
+    pub requests: Mutex<Vec<TranslateTextRequest>>,
+}
+
+impl TranslationProvider for Recorder {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("[fr] {}", text),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
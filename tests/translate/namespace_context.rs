@@ -0,0 +1,41 @@
+use crate::mock::Contextual;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn namespace_context() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/namespace_context.yaml")?;
+    assert_eq!(
+        Some("Privacy settings"),
+        intl.namespace_context("settings.privacy.title")
+    );
+    assert_eq!(
+        Some("Settings screen"),
+        intl.namespace_context("settings.title")
+    );
+    assert_eq!(None, intl.namespace_context("settingsTitle"));
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Contextual, options).await?;
+    let lookup = |key: &str| {
+        result
+            .translated
+            .lookup(key)
+            .and_then(|value| value.value().as_str().map(|s| s.to_owned()))
+    };
+    assert_eq!(
+        Some("[Settings screen] Settings"),
+        lookup("settings.title").as_deref()
+    );
+    assert_eq!(
+        Some("[Privacy settings] Privacy"),
+        lookup("settings.privacy.title").as_deref()
+    );
+    assert_eq!(
+        Some("[Analytics toggle] Tracking"),
+        lookup("settings.privacy.tracking").as_deref()
+    );
+    assert_eq!(Some("[] Settings"), lookup("settingsTitle").as_deref());
+    Ok(())
+}
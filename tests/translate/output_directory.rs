@@ -0,0 +1,42 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn output_directory() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("arb-out-dir-{}", std::process::id()));
+    let cache_path = "tests/fixtures/output_directory/.cache.json";
+    let cache = std::fs::read(cache_path)?;
+
+    let mut intl = Intl::new("tests/fixtures/output_directory.yaml")?;
+    intl.set_output_dir(Some(dir.clone()));
+    intl.ensure_output_directory()?;
+
+    // Existing translation and cache are read from the arb-dir
+    let result = intl
+        .translate(&Marked, TranslationOptions::new(Lang::Fr))
+        .await?;
+    assert_eq!(1, result.length);
+    let value = result.translated.lookup("greeting").unwrap();
+    assert_eq!(Some("Bonjour"), value.value().as_str());
+
+    // Cache is written to the output directory
+    assert_eq!(cache, std::fs::read(cache_path)?);
+    let written = std::fs::read_to_string(dir.join(".cache.json"))?;
+    assert!(written.contains("Goodbye"));
+
+    // Translations are written to the output directory
+    let files = intl.split_translation("fr", &result.translated)?;
+    assert_eq!(1, files.len());
+    let (file_path, file) = &files[0];
+    assert_eq!(&dir.join("app_fr.arb"), file_path);
+    std::fs::write(file_path, serde_json::to_string_pretty(file)?)?;
+
+    // Written translation reads back from the output directory
+    let output = intl.load_file(file_path)?;
+    let value = output.lookup("farewell").unwrap();
+    assert_eq!(Some("[fr] Goodbye"), value.value().as_str());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
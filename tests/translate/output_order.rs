@@ -0,0 +1,32 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, OutputOrder, TranslationOptions};
+
+#[tokio::test]
+pub async fn output_order_sorted() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/basic.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.output_order = OutputOrder::Sorted;
+    options.disable_cache = true;
+    let result = intl.translate(&Marked, options).await?;
+
+    let keys = result
+        .translated
+        .entries()
+        .iter()
+        .map(|entry| entry.key().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec!["@@locale", "@_END", "helloName", "@helloName", "helloWorld"],
+        keys
+    );
+    Ok(())
+}
+
+#[test]
+pub fn output_order_parse() -> Result<()> {
+    assert_eq!(OutputOrder::Sorted, "sorted".parse()?);
+    assert_eq!(OutputOrder::Preserve, "preserve".parse()?);
+    assert!("random".parse::<OutputOrder>().is_err());
+    Ok(())
+}
@@ -8,7 +8,7 @@ use std::collections::HashMap;
 
 #[tokio::test]
 pub async fn overrides() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
 
     let mut overrides_file = ArbFile::default();
     let value = Value::String("Salut".to_string());
@@ -23,10 +23,27 @@ pub async fn overrides() -> Result<()> {
         dry_run: false,
         invalidation: None,
         overrides: Some(overrides),
+        post_process: None,
+        glossary_id: None,
+        global_context: None,
+        markup: Default::default(),
+        collect_errors: false,
+        target_code: None,
+        formality: None,
+        source_hash: false,
+        character_budget: None,
+        metadata_filter: None,
+        keep_on_empty: true,
+        retry_preserve_formatting: true,
+        output_order: Default::default(),
+        baseline: None,
+        placeholder_strategy: Default::default(),
+        ignore_whitespace: false,
+        existing: None,
         disable_cache: false,
     };
 
-    let mut intl = Intl::new(index)?;
+    let intl = Intl::new(index)?;
     let result = intl.translate(&api, options).await?;
     // Not translated because overriden
     assert_eq!(0, result.length);
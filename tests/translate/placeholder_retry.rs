@@ -0,0 +1,124 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    entity_tag, markdown_tag, Error, Intl, Markup, TranslationOptions, TranslationProvider,
+};
+use std::sync::Mutex;
+
+/// Provider that only keeps placeholders when formatting
+/// is preserved.
+struct Lossy;
+
+impl TranslationProvider for Lossy {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        // Retries protect placeholders with sentinels
+        if request.tag_handling.is_none() {
+            assert!(request.text.iter().all(|text| text.contains('\u{e000}')));
+        }
+        let keep = request.tag_handling.is_none() && request.preserve_formatting == Some(true);
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: if keep {
+                        format!("[fr] {}", text)
+                    } else {
+                        "[fr]".to_owned()
+                    },
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn placeholder_retry() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/basic.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Lossy, options).await?;
+    let value = result.translated.lookup("helloName").unwrap();
+    assert_eq!(Some("[fr] Hello {name}"), value.value().as_str());
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.retry_preserve_formatting = false;
+    let result = intl.translate(&Lossy, options).await;
+    assert!(matches!(result, Err(Error::PlaceholderLost(_, _))));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn placeholder_retry_skipped() -> Result<()> {
+    let mut intl = Intl::new("tests/fixtures/basic.yaml")?;
+    intl.set_defer_cache(true);
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.collect_errors = true;
+    options.retry_preserve_formatting = false;
+    let result = intl.translate(&Lossy, options).await?;
+    assert_eq!(1, result.errors.len());
+    assert_eq!("helloName", result.errors[0].0);
+    assert!(result.cache_changed);
+
+    // Skipped keys are not cached so they are retried
+    let cache = intl.cache();
+    let file = cache.get_file(&Lang::Fr).unwrap();
+    assert!(file.contains_key("helloWorld"));
+    assert!(!file.contains_key("helloName"));
+    Ok(())
+}
+
+/// Provider that drops placeholder tags and keeps other tags.
+#[derive(Default)]
+struct DropPlaceholders {
+    requests: Mutex<Vec<TranslateTextRequest>>,
+}
+
+impl TranslationProvider for DropPlaceholders {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("[fr] {}", text.replace("<ph>", "").replace("</ph>", "")),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn placeholder_retry_tagged() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/placeholder_tagged.yaml")?;
+    let api = DropPlaceholders::default();
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.markup = Markup::Markdown;
+    let result = intl.translate(&api, options).await?;
+    let value = result.translated.lookup("share").unwrap();
+    assert_eq!(
+        Some("[fr] Share with {name} &amp; run `arb`"),
+        value.value().as_str()
+    );
+
+    // Retry keeps the Markdown and entity tags
+    let requests = api.requests.lock().unwrap();
+    assert_eq!(2, requests.len());
+    let retry = &requests[1];
+    assert!(retry.text[0].contains('\u{e000}'));
+    assert!(retry.text[0].contains(&format!("<{}>", entity_tag())));
+    assert!(retry.text[0].contains(&format!("<{}>", markdown_tag())));
+    assert!(retry.tag_handling.is_some());
+    Ok(())
+}
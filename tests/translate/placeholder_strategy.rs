@@ -0,0 +1,41 @@
+use crate::mock::Recorder;
+use anyhow::Result;
+use arb_lib::{
+    deepl::{Lang, TranslateTextRequest},
+    Intl, PlaceholderStrategy, TranslationOptions,
+};
+
+async fn translate(strategy: PlaceholderStrategy) -> Result<(String, TranslateTextRequest)> {
+    let intl = Intl::new("tests/fixtures/placeholder_strategy.yaml")?;
+    let provider = Recorder::default();
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.placeholder_strategy = strategy;
+    let result = intl.translate(&provider, options).await?;
+    let value = result.translated.lookup("inbox").unwrap();
+    let translation = value.value().as_str().unwrap().to_owned();
+    let mut requests = provider.requests.into_inner().unwrap();
+    assert_eq!(1, requests.len());
+    Ok((translation, requests.remove(0)))
+}
+
+#[tokio::test]
+pub async fn placeholder_strategy() -> Result<()> {
+    let expected = "[fr] Hello {name}, you have {count} messages from {sender}";
+
+    let (translation, request) = translate(PlaceholderStrategy::XmlTags).await?;
+    assert_eq!(expected, translation);
+    assert!(request.tag_handling.is_some());
+    assert_eq!(
+        "Hello <ph>name</ph>, you have <ph>count</ph> messages from <ph>sender</ph>",
+        request.text[0]
+    );
+
+    let (translation, request) = translate(PlaceholderStrategy::Sentinel).await?;
+    assert_eq!(expected, translation);
+    assert!(request.tag_handling.is_none());
+    assert!(request.ignore_tags.is_none());
+    assert_eq!(3, request.text[0].matches('\u{e000}').count());
+    assert!(!request.text[0].contains('{'));
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    Error, Intl, SkipReason, TranslationOptions, TranslationProvider,
+};
+
+/// Provider that moves the placeholder from the `other`
+/// branch to the `one` branch.
+struct Moved;
+
+impl TranslationProvider for Moved {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: text
+                        .replace("one{# item}", "one{<ph>count</ph> article}")
+                        .replace("other{<ph>count</ph> items}", "other{articles}"),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn plural_branches() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/plural_branches.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Moved, options).await;
+    assert!(matches!(
+        result,
+        Err(Error::PlaceholderMissingInBranch(name, branch, _)) if name == "count" && branch == "other"
+    ));
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    options.collect_errors = true;
+    let result = intl.translate(&Moved, options).await?;
+    assert_eq!(1, result.errors.len());
+    assert_eq!("items", result.errors[0].0);
+    assert!(result.translated.lookup("items").is_none());
+    assert!(result
+        .skipped
+        .iter()
+        .any(|skipped| skipped.key == "items" && skipped.reason == SkipReason::Invalid));
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::mock::Marked;
+use anyhow::Result;
+use arb_lib::{deepl::Lang, Intl, TranslationOptions};
+
+#[tokio::test]
+pub async fn post_process() -> Result<()> {
+    let mut intl = Intl::new("tests/fixtures/post_process.yaml")?;
+    intl.set_defer_cache(true);
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.post_process = Some(Box::new(|text: &str| text.to_uppercase()));
+    let result = intl.translate(&Marked, options).await?;
+    assert_eq!(1, result.length);
+
+    // Only the translated value is processed
+    let value = result.translated.lookup("farewell").unwrap();
+    assert_eq!(Some("[FR] GOODBYE"), value.value().as_str());
+    let value = result.translated.lookup("greeting").unwrap();
+    assert_eq!(Some("Bonjour"), value.value().as_str());
+    Ok(())
+}
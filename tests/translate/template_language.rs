@@ -0,0 +1,35 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{ApiOptions, DeeplApi, Lang},
+    Intl, TranslationOptions,
+};
+
+#[tokio::test]
+pub async fn template_language() -> Result<()> {
+    // No API calls are made so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/basic.yaml";
+    let intl = Intl::new(index)?;
+    let mut options = TranslationOptions::new(Lang::En);
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(0, result.length);
+    assert_eq!(result.template.len(), result.translated.len());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn template_language_code() -> Result<()> {
+    // Raw code naming the template language must not be translated
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let index = "tests/fixtures/basic.yaml";
+    let intl = Intl::new(index)?;
+    let mut options = TranslationOptions::new(Lang::En);
+    options.target_code = Some("en".to_owned());
+    options.disable_cache = true;
+    let result = intl.translate(&api, options).await?;
+    assert_eq!(0, result.length);
+    assert!(!result.changed);
+    assert_eq!(result.template.len(), result.translated.len());
+    Ok(())
+}
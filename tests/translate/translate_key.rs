@@ -0,0 +1,87 @@
+use crate::mock::{Marked, Recorder};
+use anyhow::Result;
+use arb_lib::{
+    deepl::Lang, markdown_tag, ArbEntry, ArbFile, Error, Intl, Markup, SkipReason,
+    TranslationOptions,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[tokio::test]
+pub async fn translate_key() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/basic.yaml")?;
+    let translation = intl
+        .translate_key(&Marked, TranslationOptions::new(Lang::Fr), "helloName")
+        .await?;
+    assert_eq!("[fr] Hello {name}", translation);
+
+    let result = intl
+        .translate_key(&Marked, TranslationOptions::new(Lang::Fr), "missing")
+        .await;
+    assert!(matches!(result, Err(Error::UnknownKey(_))));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_key_options() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/translate_key.yaml")?;
+    let api = Recorder::default();
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.glossary_id = Some("glossary".to_string());
+    options.markup = Markup::Markdown;
+    let translation = intl.translate_key(&api, options, "intro").await?;
+    assert_eq!("[fr] Read the `docs` first", translation);
+
+    // Only the key is sent with the glossary and markup protected
+    let requests = api.requests.lock().unwrap();
+    assert_eq!(1, requests.len());
+    assert_eq!(1, requests[0].text.len());
+    assert_eq!(Some("glossary"), requests[0].glossary_id.as_deref());
+    assert!(requests[0].text[0].contains(&format!("<{}", markdown_tag())));
+    assert!(!requests[0].text[0].contains('`'));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_key_override() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/translate_key.yaml")?;
+    let api = Recorder::default();
+
+    let mut overrides_file = ArbFile::default();
+    let value = Value::String("Salut".to_string());
+    overrides_file.insert_entry(ArbEntry::new("greeting", &value));
+    let mut overrides = HashMap::new();
+    overrides.insert(Lang::Fr, overrides_file);
+
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.overrides = Some(overrides);
+    let translation = intl.translate_key(&api, options, "greeting").await?;
+    assert_eq!("Salut", translation);
+    assert!(api.requests.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_key_locked() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/translate_key.yaml")?;
+    let api = Recorder::default();
+    let translation = intl
+        .translate_key(&api, TranslationOptions::new(Lang::Fr), "terms")
+        .await?;
+    assert_eq!("Conditions d'utilisation", translation);
+    assert!(api.requests.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_key_skipped() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/translate_key.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.character_budget = Some(0);
+    let result = intl.translate_key(&Marked, options, "intro").await;
+    assert!(matches!(
+        result,
+        Err(Error::KeySkipped(key, SkipReason::TooLong)) if key == "intro"
+    ));
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use arb_lib::deepl::{ApiOptions, DeeplApi, Lang, TranslateTextRequest, MAX_TEXTS_PER_REQUEST};
+
+#[tokio::test]
+pub async fn translate_keyed_empty() -> Result<()> {
+    // No texts are sent so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let options = TranslateTextRequest::new(vec![], Lang::Fr);
+    let results = api
+        .translate_keyed(Vec::<(String, String)>::new(), &options)
+        .await?;
+    assert!(results.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_keyed_chunks() -> Result<()> {
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
+    let mut options = TranslateTextRequest::new(vec![], Lang::Fr);
+    options.source_lang = Some(Lang::En);
+    let entries: Vec<(usize, String)> = (0..MAX_TEXTS_PER_REQUEST + 5)
+        .map(|index| (index, format!("Page {}", index)))
+        .collect();
+    let results = api.translate_keyed(entries, &options).await?;
+    assert_eq!(MAX_TEXTS_PER_REQUEST + 5, results.len());
+    for (index, (key, text)) in results.into_iter().enumerate() {
+        assert_eq!(index, key);
+        assert!(text.contains(&index.to_string()));
+    }
+    Ok(())
+}
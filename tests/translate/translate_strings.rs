@@ -0,0 +1,50 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{
+        self, ApiOptions, DeeplApi, Lang, TextTranslation, TranslateTextRequest,
+        TranslateTextResponse,
+    },
+    translate_strings, TranslationProvider,
+};
+
+/// Provider that asserts the source language is a base code.
+struct BaseSource;
+
+impl TranslationProvider for BaseSource {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        assert_eq!(Some(Lang::En), request.source_lang);
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: text.to_owned(),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn translate_strings_empty() -> Result<()> {
+    // Empty strings are not sent so the key is not used
+    let api = DeeplApi::new(ApiOptions::new("not-used"));
+    let texts = vec![String::new(), " ".to_string()];
+    let translations = translate_strings(&api, &texts, Lang::Fr.into(), None).await?;
+    assert_eq!(texts, translations);
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn translate_strings_base_source() -> Result<()> {
+    // Regional variants are not valid source languages
+    let texts = vec!["Hello".to_string()];
+    let translations =
+        translate_strings(&BaseSource, &texts, Lang::Fr.into(), Some(Lang::EnGb)).await?;
+    assert_eq!(texts, translations);
+    Ok(())
+}
@@ -1,10 +1,27 @@
 use anyhow::Result;
-use arb_lib::deepl::{ApiOptions, DeeplApi};
+use arb_lib::deepl::{ApiOptions, DeeplApi, Plan};
 
 #[tokio::test]
 pub async fn usage() -> Result<()> {
-    let api = DeeplApi::new(ApiOptions::new(&std::env::var("DEEPL_API_KEY").unwrap()));
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
     let usage = api.usage().await?;
     assert!(usage.character_limit > 0);
+    assert_eq!(Some(api.plan()), usage.plan);
     Ok(())
 }
+
+#[tokio::test]
+pub async fn account_status() -> Result<()> {
+    let api = DeeplApi::new(ApiOptions::new(std::env::var("DEEPL_API_KEY").unwrap()));
+    let status = api.account_status().await?;
+    assert!(status.usage.character_limit > 0);
+    assert!(!status.source_languages.is_empty());
+    assert!(!status.target_languages.is_empty());
+    Ok(())
+}
+
+#[test]
+pub fn plan() {
+    assert_eq!(Plan::Free, ApiOptions::new("key:fx").plan());
+    assert_eq!(Plan::Pro, ApiOptions::new("key").plan());
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    Intl, TranslationOptions, TranslationProvider, Warning,
+};
+
+/// Provider that detects the source language as the given language.
+struct Detected(Lang);
+
+impl TranslationProvider for Detected {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: text.clone(),
+                    detected_source_language: self.0,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn source_mismatch_warning() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/whitespace.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Detected(Lang::De), options).await?;
+
+    assert!(!result.warnings.is_empty());
+    assert!(result.warnings.iter().all(|warning| matches!(
+        warning,
+        Warning::SourceMismatch {
+            detected: Lang::De,
+            ..
+        }
+    )));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn source_mismatch_regional() -> Result<()> {
+    // Regional variants of the template language are not a mismatch
+    let intl = Intl::new("tests/fixtures/whitespace.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Detected(Lang::EnGb), options).await?;
+    assert!(!result
+        .warnings
+        .iter()
+        .any(|warning| matches!(warning, Warning::SourceMismatch { .. })));
+    Ok(())
+}
+
+#[test]
+pub fn no_skipped_files() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/template_language.yaml")?;
+    assert!(intl.skipped_files()?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use arb_lib::{
+    deepl::{self, Lang, TextTranslation, TranslateTextRequest, TranslateTextResponse},
+    Intl, TranslationOptions, TranslationProvider,
+};
+
+/// Provider that pads translations with whitespace.
+struct Padded;
+
+impl TranslationProvider for Padded {
+    async fn translate_text(
+        &self,
+        request: &TranslateTextRequest,
+    ) -> deepl::Result<TranslateTextResponse> {
+        Ok(TranslateTextResponse {
+            translations: request
+                .text
+                .iter()
+                .map(|text| TextTranslation {
+                    text: format!("{}  ", text),
+                    detected_source_language: Lang::En,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tokio::test]
+pub async fn preserve_whitespace() -> Result<()> {
+    let intl = Intl::new("tests/fixtures/whitespace.yaml")?;
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.disable_cache = true;
+    let result = intl.translate(&Padded, options).await?;
+
+    let value = result.translated.lookup("padded").unwrap();
+    assert_eq!(Some(" {name} "), value.value().as_str());
+    let value = result.translated.lookup("prefix").unwrap();
+    assert_eq!(Some("Items: "), value.value().as_str());
+    let value = result.translated.lookup("blank").unwrap();
+    assert_eq!(Some("   "), value.value().as_str());
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn ignore_whitespace_update() -> Result<()> {
+    let cache_path = "tests/fixtures/whitespace_update/.cache.json";
+    let cache = std::fs::read(cache_path)?;
+
+    let mut intl = Intl::new("tests/fixtures/whitespace_update.yaml")?;
+    intl.set_defer_cache(true);
+    let mut options = TranslationOptions::new(Lang::Fr);
+    options.ignore_whitespace = true;
+    let result = intl.translate(&Padded, options).await?;
+    assert_eq!(0, result.length);
+    assert!(result.changed);
+    assert!(result.cache_changed);
+
+    // Surrounding whitespace of the new source is restored
+    let value = result.translated.lookup("message").unwrap();
+    assert_eq!(Some(" Bonjour le monde "), value.value().as_str());
+
+    // Cache holds the current source
+    let cached = intl
+        .cache()
+        .get_file(&Lang::Fr)
+        .and_then(|file| file.lookup("message").map(|entry| entry.value().clone()))
+        .and_then(|value| value.as_str().map(|s| s.to_owned()));
+    assert_eq!(Some(" Hello  world "), cached.as_deref());
+    assert_eq!(cache, std::fs::read(cache_path)?);
+    Ok(())
+}